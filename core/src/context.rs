@@ -1,28 +1,525 @@
+use std::collections::HashMap;
+
+use proc_macro2::Span;
 use syn::{
-    Ident, Result, Token,
+    Error, Ident, LitStr, Result, Token, bracketed, parenthesized,
     parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token,
 };
 
+use naming::Naming;
+use selection::{Inclusion, Selection, Selector};
+use variant::VariantContext;
+
+use crate::utilities::errors_ext::ErrorsExt;
+
+pub mod case;
 pub mod r#enum;
 pub mod field;
 pub mod field_value;
 pub mod r#impl;
 pub mod item;
+pub mod naming;
+pub mod selection;
 pub mod stmt;
 pub mod r#struct;
+pub mod variant;
 
 pub struct Context {
     pub variants: Vec<Ident>,
+    pub variant_ctxs: HashMap<Ident, VariantContext>,
+    pub groups: HashMap<Ident, Vec<Ident>>,
+    pub naming: Naming,
+    pub default: Inclusion,
+    pub collapse_empty: bool,
+    pub split_cases: bool,
+
+    /// Whether to generate conversions between variants, and the pairs of variants to generate
+    /// them for, as in `derive_conversions(Detail -> Summary)`, or all pairs for which it is
+    /// possible if none are given.
+    pub derive_conversions: bool,
+    pub conversions: Vec<(Ident, Ident)>,
+
+    /// Whether the variants are successive versions of the base, in order of declaration.
+    pub versioned: bool,
+}
+
+impl Context {
+    /// Whether nodes without settings for `variant` are included in it, by the `default` option
+    /// of the variant, or else of its nearest ancestor that has one, or else the top-level one.
+    ///
+    pub fn includes_by_default(&self, variant: &Ident) -> bool {
+        self.variant_ctxs
+            .get(variant)
+            .and_then(|variant_ctx| {
+                variant_ctx.default.or_else(|| {
+                    variant_ctx
+                        .ancestors
+                        .iter()
+                        .find_map(|ancestor| self.variant_ctxs.get(ancestor)?.default)
+                })
+            })
+            .unwrap_or(self.default)
+            == Inclusion::Include
+    }
+
+    /// The variants selected by `ident`: the members of the group by that name, or otherwise the
+    /// variant itself.
+    ///
+    pub fn select(&self, ident: &Ident) -> Vec<Ident> {
+        match self.groups.get(ident) {
+            Some(members) => members.clone(),
+            None => vec![ident.clone()],
+        }
+    }
+
+    /// Resolve the settings of each variant from the variant attributes of a node:
+    ///
+    /// 1. variants selected by name or group in `include(..)` or `exclude(..)` take the settings
+    ///    of that attribute;
+    /// 2. variants not selected by name or group take the settings of an attribute that selects
+    ///    them with the `*` wildcard, or that excludes other variants, if any;
+    /// 3. variants that are still without settings inherit the settings of their nearest
+    ///    ancestor, if any.
+    ///
+    /// The `settings` closure creates the settings of a variant from the attribute they are taken
+    /// from, and whether the variant includes the node. Variants only included because another
+    /// variant is excluded take their settings from a default attribute instead, such that the
+    /// options of an `exclude(..)` attribute only apply to the variants it names.
+    ///
+    pub fn resolve<A: Selection + Default, S>(
+        &self,
+        attributes: &[A],
+        errors: &mut Vec<Error>,
+        settings: impl Fn(&A, &Ident, bool) -> S,
+    ) -> HashMap<Ident, S> {
+        // Each selection holds the attribute selecting the variant, whether it includes it, and
+        // whether the options of the attribute apply to it.
+        let mut selected = HashMap::<Ident, (&A, bool, bool)>::new();
+        let mut implied = Vec::<(&A, bool, bool, Span)>::new();
+
+        for attr in attributes {
+            for (selectors, include) in [(attr.include(), true), (attr.exclude(), false)] {
+                let selectors = match selectors {
+                    Some(selectors) => selectors,
+                    None => continue,
+                };
+
+                for selector in selectors {
+                    let ident = match selector {
+                        Selector::All(_) => {
+                            implied.push((attr, include, true, selector.span()));
+                            continue;
+                        }
+                        Selector::Ident(ident) => ident,
+                    };
+
+                    if !errors.contains_variant(self, ident) {
+                        continue;
+                    }
+
+                    for variant in self.select(ident) {
+                        if selected
+                            .insert(variant.clone(), (attr, include, true))
+                            .is_some()
+                        {
+                            errors.push(Error::new(
+                                ident.span(),
+                                format!("duplicate settings for variant `{}`", variant),
+                            ));
+                        }
+                    }
+                }
+
+                // Excluding some variants implies including all others, unless the attribute
+                // already selects them with the wildcard.
+                let selects_all = |selectors: Option<&Vec<Selector>>| {
+                    selectors
+                        .into_iter()
+                        .flatten()
+                        .any(|selector| matches!(selector, Selector::All(_)))
+                };
+
+                if !include && !selects_all(Some(selectors)) && !selects_all(attr.include()) {
+                    let span = selectors
+                        .first()
+                        .map(Selector::span)
+                        .unwrap_or(Span::call_site());
+
+                    implied.push((attr, true, false, span));
+                }
+            }
+        }
+
+        // Multiple wildcards or exclusions implying different settings for the same variant are
+        // ambiguous, except for the include implied by an exclusion, which carries no options and
+        // yields to a wildcard including the variant with options.
+        let mut implied_selected = HashMap::<Ident, (&A, bool, bool)>::new();
+
+        for (attr, include, scoped, span) in implied {
+            for variant in &self.variants {
+                if selected.contains_key(variant) {
+                    continue;
+                }
+
+                if let Some((_, previous_include, previous_scoped)) = implied_selected.get(variant)
+                    && include
+                    && *previous_include
+                    && scoped != *previous_scoped
+                {
+                    if scoped {
+                        implied_selected.insert(variant.clone(), (attr, include, scoped));
+                    }
+                    continue;
+                }
+
+                let previous = implied_selected.insert(variant.clone(), (attr, include, scoped));
+
+                if let Some((previous_attr, previous_include, _)) = previous
+                    && (!std::ptr::eq(previous_attr, attr) || previous_include != include)
+                {
+                    errors.push(Error::new(
+                        span,
+                        format!("conflicting settings for variant `{}`", variant),
+                    ));
+                }
+            }
+        }
+
+        selected.extend(implied_selected);
+
+        let inherited = self
+            .variants
+            .iter()
+            .filter(|variant| !selected.contains_key(*variant))
+            .filter_map(|variant| {
+                self.variant_ctxs
+                    .get(variant)?
+                    .ancestors
+                    .iter()
+                    .find_map(|ancestor| selected.get(ancestor))
+                    .map(|selection| (variant.clone(), *selection))
+            })
+            .collect::<Vec<_>>();
+
+        selected.extend(inherited);
+
+        let unscoped = A::default();
+
+        selected
+            .into_iter()
+            .map(|(variant, (attr, include, scoped))| {
+                let attr = if scoped { attr } else { &unscoped };
+                let settings = settings(attr, &variant, include);
+                (variant, settings)
+            })
+            .collect()
+    }
+
+    /// Resolve the ancestors of each variant, breadth first, such that nearer ancestors take
+    /// precedence over farther ones. Undeclared parents and inheritance cycles are rejected.
+    ///
+    fn resolve_ancestors(&mut self) -> Result<()> {
+        for variant_ctx in self.variant_ctxs.values() {
+            for parent in &variant_ctx.parents {
+                if !self.variants.contains(parent) {
+                    return Err(Error::new(
+                        parent.span(),
+                        format!("`{}` has not been declared as a variant", parent),
+                    ));
+                }
+            }
+        }
+
+        for variant in &self.variants {
+            let mut ancestors = Vec::<Ident>::new();
+            let mut queue = self.parents(variant).to_vec();
+
+            while !queue.is_empty() {
+                let ancestor = queue.remove(0);
+
+                if &ancestor == variant {
+                    return Err(Error::new(
+                        variant.span(),
+                        format!("variant `{}` inherits from itself", variant),
+                    ));
+                }
+
+                if ancestors.contains(&ancestor) {
+                    continue;
+                }
+
+                queue.extend(self.parents(&ancestor).iter().cloned());
+                ancestors.push(ancestor);
+            }
+
+            if let Some(variant_ctx) = self.variant_ctxs.get_mut(variant) {
+                variant_ctx.ancestors = ancestors;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that groups only contain declared variants, and do not shadow any of them.
+    ///
+    fn resolve_groups(&self) -> Result<()> {
+        for (group, members) in &self.groups {
+            if self.variants.contains(group) {
+                return Err(Error::new(
+                    group.span(),
+                    format!("group `{}` has the same name as a variant", group),
+                ));
+            }
+
+            for member in members {
+                if !self.variants.contains(member) {
+                    return Err(Error::new(
+                        member.span(),
+                        format!("`{}` has not been declared as a variant", member),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that conversions are only requested between declared variants.
+    ///
+    fn resolve_conversions(&self) -> Result<()> {
+        for (from, into) in &self.conversions {
+            for variant in [from, into] {
+                if !self.variants.contains(variant) {
+                    return Err(Error::new(
+                        variant.span(),
+                        format!("`{}` has not been declared as a variant", variant),
+                    ));
+                }
+            }
+
+            if from == into {
+                return Err(Error::new(
+                    into.span(),
+                    format!("cannot convert `{}` into itself", into),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parents(&self, variant: &Ident) -> &[Ident] {
+        self.variant_ctxs
+            .get(variant)
+            .map(|variant_ctx| variant_ctx.parents.as_slice())
+            .unwrap_or_default()
+    }
 }
 
 impl Parse for Context {
+    /// Parses a comma separated list of variants and item options:
+    ///
+    /// - `Variant`: declares a variant, named using the naming template;
+    /// - `Variant(derive(Debug))`: declares a variant with attributes added to its items only;
+    /// - `Variant(vis = "pub")`: declares a variant with a different visibility than the base;
+    /// - `Variant(repr = "u8")`: declares a variant whose struct or enum has a different
+    ///   representation than the base;
+    /// - `Variant: Parent + Other`: declares a variant that inherits the settings of its parents;
+    /// - `Variant as Name`: declares a variant with an exact name;
+    /// - `Variant(default = include)`: declares a variant that includes fields without settings;
+    /// - `Variant(rename(Old = New))`: declares a variant whose enums and impls name the enum case
+    ///   `Old` as `New`, unlike the `rename` option of the case, which does not reach impls;
+    /// - `Variant(kind)`: declares a variant whose enum has no fields in any case, along with
+    ///   conversions from references to the base and other variants;
+    /// - `Variant(from_base)`: declares a variant with a conversion from the base;
+    /// - `Variant(borrowed)`: declares a variant whose fields are references to those of the base,
+    ///   and `Variant(borrowed(as_variant))` along with a method creating it from the base;
+    /// - `Variant(patch)`: declares a variant with a method applying it as a patch onto the base;
+    /// - `Variant(diff)`: declares a variant with a method creating it as a patch between two
+    ///   values of the base;
+    /// - `Variant(complete)`: declares a variant with a struct holding the fields it lacks, and a
+    ///   method completing it into the base;
+    /// - `Variant(impl_default)`: declares a variant implementing `Default` with the default
+    ///   values of its fields;
+    /// - `Variant(builder)`: declares a variant with a builder setting each of its fields;
+    /// - `Variant(validate_error = "Error")`: declares a variant whose fields are validated by
+    ///   functions returning this error;
+    /// - `group(Name = [Variant, Other])`: declares a group of variants, which can be selected by
+    ///   name in `include(..)` and `exclude(..)` lists;
+    /// - `name = "{b}{v}"`: sets the naming template for all variants;
+    /// - `default = include`: includes fields without settings in all variants;
+    /// - `collapse_empty`: turns structs and enum cases without any fields left into unit
+    ///   structs and unit cases;
+    /// - `split_cases`: generates a struct for each enum case of the base and every variant, along
+    ///   with conversions from and to the enum;
+    /// - `derive_conversions`: generates conversions from each variant into every variant with a
+    ///   subset of its fields, and `derive_conversions(Detail -> Summary)` only between the given
+    ///   pairs;
+    /// - `versioned`: treats the variants as successive versions, in order of declaration, with
+    ///   fields added and removed using `since(..)` and `until(..)`, along with migrations from
+    ///   each version into the next, which `derive_conversions` then leaves out.
+    ///
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut variants = Vec::new();
+        let mut variant_ctxs = HashMap::new();
+        let mut groups = HashMap::new();
+        let mut naming = Naming::default();
+        let mut default = Inclusion::Exclude;
+        let mut collapse_empty = false;
+        let mut split_cases = false;
+        let mut derive_conversions = false;
+        let mut conversions = Vec::new();
+        let mut versioned = false;
+
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                let ident = input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+
+                match ident.to_string().as_str() {
+                    "name" => naming.set_template(input.parse::<LitStr>()?)?,
+                    "default" => default = input.parse::<Inclusion>()?,
+                    _ => {
+                        return Err(Error::new(
+                            ident.span(),
+                            format!("unknown option `{}`", ident),
+                        ));
+                    }
+                }
+            } else if input.peek(kw::collapse_empty) {
+                input.parse::<kw::collapse_empty>()?;
+                collapse_empty = true;
+            } else if input.peek(kw::split_cases) {
+                input.parse::<kw::split_cases>()?;
+                split_cases = true;
+            } else if input.peek(kw::versioned) {
+                input.parse::<kw::versioned>()?;
+                versioned = true;
+            } else if input.peek(kw::derive_conversions) {
+                input.parse::<kw::derive_conversions>()?;
+                derive_conversions = true;
+
+                if input.peek(token::Paren) {
+                    let content;
+                    parenthesized!(content in input);
+
+                    for conversion in
+                        Punctuated::<Conversion, Token![,]>::parse_terminated(&content)?
+                    {
+                        conversions.push((conversion.from, conversion.into));
+                    }
+                }
+            } else if input.peek(kw::group) {
+                input.parse::<kw::group>()?;
+
+                let content;
+                parenthesized!(content in input);
+
+                for group in Punctuated::<Group, Token![,]>::parse_terminated(&content)? {
+                    if groups.insert(group.ident.clone(), group.members).is_some() {
+                        return Err(Error::new(
+                            group.ident.span(),
+                            format!("duplicate group `{}`", group.ident),
+                        ));
+                    }
+                }
+            } else {
+                let variant_ctx = input.parse::<VariantContext>()?;
+                let ident = variant_ctx.ident.clone();
+
+                if variants.contains(&ident) {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("duplicate variant `{}`", ident),
+                    ));
+                }
+
+                if input.peek(Token![as]) {
+                    input.parse::<Token![as]>()?;
+                    naming.set_name(ident.clone(), input.parse::<Ident>()?);
+                }
+
+                variant_ctxs.insert(ident.clone(), variant_ctx);
+                variants.push(ident);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        let mut ctx = Self {
+            variants,
+            variant_ctxs,
+            groups,
+            naming,
+            default,
+            collapse_empty,
+            split_cases,
+            derive_conversions,
+            conversions,
+            versioned,
+        };
+
+        ctx.resolve_ancestors()?;
+        ctx.resolve_groups()?;
+        ctx.resolve_conversions()?;
+
+        Ok(ctx)
+    }
+}
+
+/// A named group of variants, as in `Name = [Variant, Other]`.
+///
+struct Group {
+    ident: Ident,
+    members: Vec<Ident>,
+}
+
+impl Parse for Group {
     fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        let content;
+        bracketed!(content in input);
+
         Ok(Self {
-            variants: input
-                .parse_terminated(Ident::parse, Token![,])?
-                .iter()
-                .map(|variant| variant.clone())
+            ident,
+            members: Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+                .into_iter()
                 .collect(),
         })
     }
 }
+
+/// A conversion between two variants, as in `Detail -> Summary`.
+///
+struct Conversion {
+    from: Ident,
+    into: Ident,
+}
+
+impl Parse for Conversion {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![->]>()?;
+
+        Ok(Self {
+            from,
+            into: input.parse()?,
+        })
+    }
+}
+
+mod kw {
+    syn::custom_keyword!(collapse_empty);
+    syn::custom_keyword!(derive_conversions);
+    syn::custom_keyword!(group);
+    syn::custom_keyword!(split_cases);
+    syn::custom_keyword!(versioned);
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use squattr::{attribute::Attribute, derive::Squattr};
+use syn::{Error, Expr, Ident, LitStr, Variant, visit_mut::VisitMut};
+
+use super::{
+    Context,
+    selection::{Selection, Selector},
+};
+
+/// The settings of an enum case (an enum `Variant` node), not to be confused with the variants
+/// generated by this library.
+///
+pub struct CaseContext<'a> {
+    pub context: &'a Context,
+    pub settings: HashMap<Ident, VariantSettings>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> CaseContext<'a> {
+    pub fn new(context: &'a Context) -> Self {
+        Self {
+            context,
+            settings: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl VisitMut for CaseContext<'_> {
+    fn visit_variant_mut(&mut self, node: &mut Variant) {
+        self.visit_attributes_mut(&mut node.attrs);
+    }
+
+    fn visit_attributes_mut(&mut self, node: &mut Vec<syn::Attribute>) {
+        let attributes = match VariantAttribute::from_attributes(node, "variants") {
+            Ok(attrs) => attrs,
+            Err(error) => {
+                self.errors.push(error);
+                return;
+            }
+        };
+
+        self.settings =
+            self.context
+                .resolve(&attributes, &mut self.errors, VariantAttribute::settings);
+    }
+}
+
+#[derive(Squattr, Clone, Default)]
+struct VariantAttribute {
+    include: Option<Vec<Selector>>,
+    exclude: Option<Vec<Selector>>,
+    rename: Option<LitStr>,
+    payload: Option<LitStr>,
+    discriminant: Option<Expr>,
+}
+
+impl VariantAttribute {
+    fn settings(&self, variant: &Ident, include: bool) -> VariantSettings {
+        VariantSettings {
+            variant: variant.clone(),
+            include,
+            rename: self.rename.clone(),
+            payload: self.payload.clone(),
+            discriminant: self.discriminant.clone(),
+        }
+    }
+}
+
+impl Selection for VariantAttribute {
+    fn include(&self) -> Option<&Vec<Selector>> {
+        self.include.as_ref()
+    }
+
+    fn exclude(&self) -> Option<&Vec<Selector>> {
+        self.exclude.as_ref()
+    }
+}
+
+#[derive(Clone)]
+pub struct VariantSettings {
+    pub variant: Ident,
+    pub include: bool,
+
+    /// The name of the case in the enum of the variant, as in `include(V2), rename = "New"`. Impls
+    /// are expanded by their own `#[variants(..)]` attribute, without the enum, so paths such as
+    /// `Self::Old` in them are only renamed by the top-level `V2(rename(Old = New))` option, which
+    /// must then be declared on the impl as well.
+    pub rename: Option<LitStr>,
+    pub payload: Option<LitStr>,
+    pub discriminant: Option<Expr>,
+}
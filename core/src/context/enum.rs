@@ -2,10 +2,11 @@ use std::collections::HashMap;
 
 use syn::{Error, Field, Variant, visit_mut::VisitMut};
 
-use super::{Context, field::FieldContext};
+use super::{Context, case::CaseContext, field::FieldContext};
 
 pub struct EnumContext<'a> {
     pub context: &'a Context,
+    pub case_ctxs: HashMap<Variant, CaseContext<'a>>,
     pub field_ctxs: HashMap<Field, FieldContext<'a>>,
     pub errors: Vec<Error>,
 }
@@ -14,6 +15,7 @@ impl<'a> EnumContext<'a> {
     pub fn new(context: &'a Context) -> Self {
         Self {
             context,
+            case_ctxs: HashMap::new(),
             field_ctxs: HashMap::new(),
             errors: Vec::new(),
         }
@@ -22,6 +24,33 @@ impl<'a> EnumContext<'a> {
 
 impl VisitMut for EnumContext<'_> {
     fn visit_variant_mut(&mut self, node: &mut Variant) {
+        let mut case_ctx = CaseContext::new(self.context);
+        case_ctx.visit_variant_mut(node);
+        self.errors.append(&mut case_ctx.errors);
+
+        // A case renamed both by its own setting and by the option of a variant is ambiguous.
+        for (variant, settings) in &case_ctx.settings {
+            let renames = self
+                .context
+                .variant_ctxs
+                .get(variant)
+                .and_then(|variant_ctx| variant_ctx.renames.get(&node.ident));
+
+            if let (Some(rename), Some(name)) = (&settings.rename, renames)
+                && *name != rename.value()
+            {
+                self.errors.push(Error::new(
+                    rename.span(),
+                    format!(
+                        "case `{}` is renamed to `{}` by the `rename(..)` option of variant `{}`",
+                        node.ident, name, variant
+                    ),
+                ));
+            }
+        }
+
+        self.case_ctxs.insert(node.clone(), case_ctx);
+
         self.visit_fields_mut(&mut node.fields);
     }
 
@@ -29,6 +58,19 @@ impl VisitMut for EnumContext<'_> {
         let mut field_ctx = FieldContext::new(self.context);
         field_ctx.visit_field_mut(node);
         self.errors.append(&mut field_ctx.errors);
+
+        // The fields of enum cases always have the visibility of the enum.
+        if let Some(vis) = field_ctx
+            .settings
+            .values()
+            .find_map(|settings| settings.vis.as_ref())
+        {
+            self.errors.push(Error::new(
+                vis.span(),
+                "`vis` cannot be set on the fields of enum cases",
+            ));
+        }
+
         self.field_ctxs.insert(node.clone(), field_ctx);
     }
 }
@@ -1,11 +1,18 @@
 use std::collections::HashMap;
 
+use proc_macro2::Span;
 use squattr::{attribute::Attribute, derive::Squattr};
-use syn::{Error, Field, Ident, LitStr, visit_mut::VisitMut};
+use syn::{
+    Error, Expr, Field, Ident, LitStr, Path, Result, Token,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    visit_mut::VisitMut,
+};
 
-use crate::utilities::errors_ext::ErrorsExt;
-
-use super::Context;
+use super::{
+    Context,
+    selection::{Selection, Selector},
+};
 
 pub struct FieldContext<'a> {
     pub context: &'a Context,
@@ -37,42 +44,236 @@ impl VisitMut for FieldContext<'_> {
             }
         };
 
-        self.settings = attributes.iter().fold(HashMap::new(), |mut acc, attr| {
-            for variant in &attr.include {
-                if !self
-                    .errors
-                    .contains_variant(&self.context.variants, &variant)
-                {
-                    continue;
+        self.settings =
+            self.context
+                .resolve(&attributes, &mut self.errors, VariantAttribute::settings);
+
+        if self.context.versioned {
+            self.resolve_versions(&attributes);
+        } else if let Some(attr) = attributes
+            .iter()
+            .find(|attr| attr.since.is_some() || attr.until.is_some())
+        {
+            let span = attr
+                .since
+                .iter()
+                .flatten()
+                .map(Since::span)
+                .chain(attr.until.iter().flatten().map(Ident::span))
+                .next()
+                .unwrap_or_else(Span::call_site);
+
+            self.errors.push(Error::new(
+                span,
+                "`since` and `until` require the variants to be `versioned`",
+            ));
+        }
+    }
+}
+
+impl FieldContext<'_> {
+    /// Include the field in the versions from its `since(..)` version, and up to but excluding
+    /// its `until(..)` version, or in all versions if these are not set. Versions with settings
+    /// of their own keep them. Versions before `since(..)` take its default, to migrate from them.
+    ///
+    fn resolve_versions(&mut self, attributes: &[VariantAttribute]) {
+        let context = self.context;
+        let variants = &context.variants;
+
+        let mut since = None;
+        let mut until = None;
+        let mut default = None;
+
+        for attr in attributes {
+            for arg in attr.since.iter().flatten() {
+                match arg {
+                    Since::Version(version) if since.replace(version).is_some() => {
+                        self.errors
+                            .push(Error::new(version.span(), "duplicate `since`"));
+                    }
+                    Since::Default(expr) if default.replace(expr).is_some() => {
+                        self.errors
+                            .push(Error::new_spanned(expr, "duplicate `default`"));
+                    }
+                    _ => {}
                 }
+            }
 
-                if let Some(_) = acc.insert(
-                    variant.clone(),
-                    VariantSettings {
-                        variant: variant.clone(),
-                        retype: attr.retype.clone(),
-                    },
-                ) {
+            for version in attr.until.iter().flatten() {
+                if until.replace(version).is_some() {
+                    self.errors
+                        .push(Error::new(version.span(), "duplicate `until`"));
+                }
+            }
+        }
+
+        if let (None, Some(expr)) = (since, default) {
+            self.errors.push(Error::new_spanned(
+                expr,
+                "`default` requires a version, as in `since(V2, default = ..)`",
+            ));
+        }
+
+        let mut position = |version: Option<&Ident>, unset: usize| match version {
+            Some(version) => match variants.iter().position(|variant| variant == version) {
+                Some(position) => Some(position),
+                None => {
                     self.errors.push(Error::new(
-                        variant.span(),
-                        format!("duplicate settings for variant `{}`", variant),
+                        version.span(),
+                        format!("`{}` has not been declared as a variant", version),
                     ));
+                    None
                 }
+            },
+            None => Some(unset),
+        };
+
+        let range = (position(since, 0), position(until, variants.len()));
+
+        let (since, until) = match range {
+            (Some(start), Some(end)) if start < end => (start, end),
+            (Some(_), Some(_)) => {
+                self.errors.push(Error::new(
+                    until.map(Ident::span).unwrap_or_else(Span::call_site),
+                    "the `since` version must precede the `until` version",
+                ));
+                return;
+            }
+            _ => return,
+        };
+
+        for (index, variant) in variants.iter().enumerate() {
+            if self.settings.contains_key(variant) {
+                continue;
             }
 
-            acc
-        });
+            self.settings.insert(
+                variant.clone(),
+                VariantSettings {
+                    variant: variant.clone(),
+                    include: (since..until).contains(&index),
+                    retype: None,
+                    vis: None,
+                    convert: None,
+                    project: None,
+                    validate: None,
+                    complete: None,
+                    migrate: default.filter(|_| index < since).cloned(),
+                    default: None,
+                },
+            );
+        }
     }
 }
 
-#[derive(Squattr, Clone)]
+#[derive(Squattr, Clone, Default)]
 struct VariantAttribute {
-    include: Vec<Ident>,
+    include: Option<Vec<Selector>>,
+    exclude: Option<Vec<Selector>>,
     retype: Option<LitStr>,
+    vis: Option<LitStr>,
+    convert: Option<Path>,
+    project: Option<Path>,
+    validate: Option<Path>,
+    complete: Option<Expr>,
+    default: Option<Expr>,
+    since: Option<Vec<Since>>,
+    until: Option<Vec<Ident>>,
+}
+
+impl VariantAttribute {
+    fn settings(&self, variant: &Ident, include: bool) -> VariantSettings {
+        VariantSettings {
+            variant: variant.clone(),
+            include,
+            retype: self.retype.clone(),
+            vis: self.vis.clone(),
+            convert: self.convert.clone(),
+            project: self.project.clone(),
+            validate: self.validate.clone(),
+            complete: self.complete.clone(),
+            migrate: None,
+            default: self.default.clone(),
+        }
+    }
+}
+
+impl Selection for VariantAttribute {
+    fn include(&self) -> Option<&Vec<Selector>> {
+        self.include.as_ref()
+    }
+
+    fn exclude(&self) -> Option<&Vec<Selector>> {
+        self.exclude.as_ref()
+    }
+}
+
+/// An argument of `since(V2, default = expr)`: the first version with the field, or the value of
+/// the field when migrating from earlier versions.
+///
+#[derive(Clone)]
+enum Since {
+    Version(Ident),
+    Default(Expr),
+}
+
+impl Since {
+    fn span(&self) -> Span {
+        match self {
+            Self::Version(version) => version.span(),
+            Self::Default(expr) => expr.span(),
+        }
+    }
+}
+
+impl Parse for Since {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![default]) {
+            input.parse::<Token![default]>()?;
+            input.parse::<Token![=]>()?;
+            Ok(Self::Default(input.parse()?))
+        } else {
+            Ok(Self::Version(input.parse()?))
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct VariantSettings {
     pub variant: Ident,
+    pub include: bool,
     pub retype: Option<LitStr>,
+    pub vis: Option<LitStr>,
+
+    /// The function converting the base value of the field into its value in the variant, used
+    /// by generated conversions.
+    pub convert: Option<Path>,
+
+    /// The function transforming the base value of the field before it is converted into the
+    /// variant, such as one masking an email, used by generated conversions from the base. It is
+    /// scoped to variants like any other setting, as in `include(Public), project = mask`, rather
+    /// than with its own list, as in `project(Public) = mask`, such that it is selected the same
+    /// way as `retype` and `convert`. It is applied before `convert`, or `Into` for a retyped
+    /// field, such that the conversion of a type is kept apart from the transform of a value.
+    /// Borrowed variants cannot have it, as their fields only reference those of the base.
+    pub project: Option<Path>,
+
+    /// The function validating the value of the field in the variant, returning a `Result<(), E>`
+    /// where `E` is the `validate_error` of the variant.
+    pub validate: Option<Path>,
+
+    /// The value of the field where it is missing from the variant, completing the variant into
+    /// the base with `complete`, as in `exclude(Create), complete = 0`.
+    pub complete: Option<Expr>,
+
+    /// The value of the field where it is missing from the version, migrating the version into
+    /// the next, as set by `since(V2, default = ..)` on the versions before `V2`.
+    pub migrate: Option<Expr>,
+
+    /// The default value of the field in the variant, used by the `Default` implementation of
+    /// `impl_default`, and as the fallback of the builder for a field that was never set. It is
+    /// scoped to variants like any other setting, as in `include(Update), default = 0`, rather
+    /// than with its own list, as in `default(Update) = 0`, so the same field can take a different
+    /// value in each variant.
+    pub default: Option<Expr>,
 }
@@ -3,9 +3,10 @@ use std::collections::HashMap;
 use squattr::{attribute::Attribute, derive::Squattr};
 use syn::{Error, FieldValue, Ident, visit_mut::VisitMut};
 
-use crate::utilities::errors_ext::ErrorsExt;
-
-use super::Context;
+use super::{
+    Context,
+    selection::{Selection, Selector},
+};
 
 pub struct FieldValueContext<'a> {
     pub context: &'a Context,
@@ -37,39 +38,39 @@ impl VisitMut for FieldValueContext<'_> {
             }
         };
 
-        self.settings = attributes.iter().fold(HashMap::new(), |mut acc, attr| {
-            for variant in &attr.include {
-                if !self
-                    .errors
-                    .contains_variant(&self.context.variants, &variant)
-                {
-                    continue;
-                }
+        self.settings =
+            self.context
+                .resolve(&attributes, &mut self.errors, VariantAttribute::settings);
+    }
+}
 
-                if let Some(_) = acc.insert(
-                    variant.clone(),
-                    VariantSettings {
-                        variant: variant.clone(),
-                    },
-                ) {
-                    self.errors.push(Error::new(
-                        variant.span(),
-                        format!("duplicate settings for variant `{}`", variant),
-                    ));
-                }
-            }
+#[derive(Squattr, Clone, Default)]
+struct VariantAttribute {
+    include: Option<Vec<Selector>>,
+    exclude: Option<Vec<Selector>>,
+}
 
-            acc
-        });
+impl VariantAttribute {
+    fn settings(&self, variant: &Ident, include: bool) -> VariantSettings {
+        VariantSettings {
+            variant: variant.clone(),
+            include,
+        }
     }
 }
 
-#[derive(Squattr, Clone)]
-struct VariantAttribute {
-    include: Vec<Ident>,
+impl Selection for VariantAttribute {
+    fn include(&self) -> Option<&Vec<Selector>> {
+        self.include.as_ref()
+    }
+
+    fn exclude(&self) -> Option<&Vec<Selector>> {
+        self.exclude.as_ref()
+    }
 }
 
 #[derive(Clone)]
 pub struct VariantSettings {
     pub variant: Ident,
+    pub include: bool,
 }
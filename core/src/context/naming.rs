@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use syn::{Error, Ident, LitStr, Result};
+
+use crate::utilities::ident_ext::IdentExt;
+
+/// Determines the identifiers of the items generated for each variant.
+///
+#[derive(Default)]
+pub struct Naming {
+    template: Option<LitStr>,
+    names: HashMap<Ident, Ident>,
+}
+
+impl Naming {
+    /// Set a template from which variant identifiers are formed. The `{b}` and `{v}` placeholders
+    /// are replaced by the base and variant identifiers respectively.
+    ///
+    pub fn set_template(&mut self, template: LitStr) -> Result<()> {
+        let value = template.value();
+
+        if !value.contains("{v}") {
+            return Err(Error::new(
+                template.span(),
+                "naming template must contain the `{v}` placeholder",
+            ));
+        }
+
+        let example = value.replace("{b}", "Base").replace("{v}", "Variant");
+        if syn::parse_str::<Ident>(&example).is_err() {
+            return Err(Error::new(
+                template.span(),
+                "naming template must produce a valid identifier",
+            ));
+        }
+
+        self.template = Some(template);
+        Ok(())
+    }
+
+    /// Use `name` as the exact identifier of the items generated for `variant`, regardless of the
+    /// base identifier.
+    ///
+    pub fn set_name(&mut self, variant: Ident, name: Ident) {
+        self.names.insert(variant, name);
+    }
+
+    /// Create the identifier of the item generated for `variant`, from an item named `base`.
+    ///
+    pub fn ident(&self, base: &Ident, variant: &Ident) -> Ident {
+        if let Some(name) = self.names.get(variant) {
+            return name.clone();
+        }
+
+        self.compose(base, variant)
+    }
+
+    /// Create the identifier of an item named after both `base` and `appendix` following the
+    /// template, such as the struct split from the enum case `appendix` of the enum `base`.
+    ///
+    pub fn compose(&self, base: &Ident, appendix: &Ident) -> Ident {
+        match &self.template {
+            Some(template) => Ident::new(
+                &template
+                    .value()
+                    .replace("{b}", &base.to_string())
+                    .replace("{v}", &appendix.to_string()),
+                appendix.span(),
+            ),
+            None => base.from_appendix(appendix),
+        }
+    }
+}
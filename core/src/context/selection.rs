@@ -0,0 +1,65 @@
+use proc_macro2::Span;
+use syn::{
+    Error, Ident, Result, Token,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+};
+
+/// Variant attributes that select the variants their settings apply to, using `include(..)` and
+/// `exclude(..)` lists.
+///
+pub trait Selection {
+    fn include(&self) -> Option<&Vec<Selector>>;
+    fn exclude(&self) -> Option<&Vec<Selector>>;
+}
+
+/// An entry of an `include(..)` or `exclude(..)` list: either a variant, a group of variants, or
+/// the `*` wildcard selecting all variants.
+///
+#[derive(Clone, Debug)]
+pub enum Selector {
+    All(Token![*]),
+    Ident(Ident),
+}
+
+impl Selector {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::All(token) => token.span(),
+            Self::Ident(ident) => ident.span(),
+        }
+    }
+}
+
+impl Parse for Selector {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![*]) {
+            Ok(Self::All(input.parse()?))
+        } else {
+            Ok(Self::Ident(input.parse()?))
+        }
+    }
+}
+
+/// Whether nodes without settings for a variant are included in that variant.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Inclusion {
+    Include,
+    Exclude,
+}
+
+impl Parse for Inclusion {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+
+        match ident.to_string().as_str() {
+            "include" => Ok(Self::Include),
+            "exclude" => Ok(Self::Exclude),
+            _ => Err(Error::new(
+                ident.span(),
+                "expected either `include` or `exclude`",
+            )),
+        }
+    }
+}
@@ -3,9 +3,10 @@ use std::collections::HashMap;
 use squattr::{attribute::Attribute, derive::Squattr};
 use syn::{Error, Expr, Ident, Stmt, spanned::Spanned, visit_mut::VisitMut};
 
-use crate::utilities::errors_ext::ErrorsExt;
-
-use super::Context;
+use super::{
+    Context,
+    selection::{Selection, Selector},
+};
 
 pub struct StmtContext<'a> {
     pub context: &'a Context,
@@ -49,69 +50,39 @@ impl VisitMut for StmtContext<'_> {
             }
         };
 
-        self.settings = attributes.iter().fold(HashMap::new(), |mut acc, attr| {
-            if let Some(include) = &attr.include {
-                for variant in include {
-                    if !self
-                        .errors
-                        .contains_variant(&self.context.variants, variant)
-                    {
-                        continue;
-                    }
-
-                    if let Some(_) = acc.insert(
-                        variant.clone(),
-                        VariantSettings {
-                            variant: variant.clone(),
-                            include: true,
-                            vary_type: attr.vary_type,
-                        },
-                    ) {
-                        self.errors.push(Error::new(
-                            variant.span(),
-                            format!("duplicate settings for variant `{}`", variant),
-                        ));
-                    }
-                }
-            }
-
-            if let Some(exclude) = &attr.exclude {
-                for variant in exclude {
-                    if !self
-                        .errors
-                        .contains_variant(&self.context.variants, variant)
-                    {
-                        continue;
-                    }
-
-                    if let Some(_) = acc.insert(
-                        variant.clone(),
-                        VariantSettings {
-                            variant: variant.clone(),
-                            include: false,
-                            vary_type: attr.vary_type,
-                        },
-                    ) {
-                        self.errors.push(Error::new(
-                            variant.span(),
-                            format!("duplicate settings for variant `{}`", variant),
-                        ));
-                    }
-                }
-            }
-
-            acc
-        });
+        self.settings =
+            self.context
+                .resolve(&attributes, &mut self.errors, VariantAttribute::settings);
     }
 }
 
-#[derive(Squattr, Clone, Debug)]
+#[derive(Squattr, Clone, Debug, Default)]
 struct VariantAttribute {
-    include: Option<Vec<Ident>>,
-    exclude: Option<Vec<Ident>>,
+    include: Option<Vec<Selector>>,
+    exclude: Option<Vec<Selector>>,
     vary_type: bool,
 }
 
+impl VariantAttribute {
+    fn settings(&self, variant: &Ident, include: bool) -> VariantSettings {
+        VariantSettings {
+            variant: variant.clone(),
+            include,
+            vary_type: self.vary_type,
+        }
+    }
+}
+
+impl Selection for VariantAttribute {
+    fn include(&self) -> Option<&Vec<Selector>> {
+        self.include.as_ref()
+    }
+
+    fn exclude(&self) -> Option<&Vec<Selector>> {
+        self.exclude.as_ref()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct VariantSettings {
     pub variant: Ident,
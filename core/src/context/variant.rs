@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    Attribute, Error, Expr, ExprLit, Ident, Lit, LitStr, Meta, MetaNameValue, Result, Token, Type,
+    Visibility, parenthesized,
+    parse::{Parse, ParseStream},
+    parse_quote, parse2,
+    punctuated::Punctuated,
+    spanned::Spanned,
+};
+
+use super::selection::Inclusion;
+
+/// The options of a single variant declared in the top-level attribute, as in
+/// `Variant(derive(Debug), vis = "pub"): Parent`. Any meta item that is not a known option is
+/// added as an attribute to the items generated for the variant.
+///
+pub struct VariantContext {
+    pub ident: Ident,
+    pub attrs: Vec<Attribute>,
+    pub vis: Option<Visibility>,
+
+    /// The representation of the struct or enum generated for the variant, replacing any
+    /// `#[repr(..)]` of the base, as in `repr = "u8"`.
+    pub repr: Option<TokenStream>,
+    pub default: Option<Inclusion>,
+
+    /// Enum cases renamed in the items generated for the variant, as in `rename(Old = New)`.
+    pub renames: HashMap<Ident, Ident>,
+
+    /// Whether the variant is a fieldless "kind" of an enum, as in `Kind(kind)`.
+    pub kind: bool,
+
+    /// Whether to generate a conversion from the base into the variant, as in `Summary(from_base)`.
+    pub from_base: bool,
+
+    /// Whether the variant borrows its fields from the base, as in `Summary(borrowed)`, and the
+    /// name of the method creating the variant from a reference to the base, as in
+    /// `Summary(borrowed(as_summary))`.
+    pub borrowed: bool,
+    pub accessor: Option<Ident>,
+
+    /// Whether to generate a method applying the variant as a patch onto the base, as in
+    /// `Update(patch)`.
+    pub patch: bool,
+
+    /// Whether to generate a method creating the variant as a patch from two values of the base,
+    /// as in `Update(diff)`.
+    pub diff: bool,
+
+    /// Whether to generate a struct with the fields the variant lacks, and a method completing the
+    /// variant into the base with it, as in `Create(complete)`.
+    pub complete: bool,
+
+    /// Whether to implement `Default` for the variant, with the field-level `default` values, as
+    /// in `Update(impl_default)`.
+    pub impl_default: bool,
+
+    /// Whether to generate a builder for the variant, as in `Summary(builder)`.
+    pub builder: bool,
+
+    /// The error type of the field validators of the variant, as in
+    /// `Create(validate_error = "CreateError")`, required by the generated `validate` method.
+    pub validate_error: Option<Type>,
+
+    pub parents: Vec<Ident>,
+
+    /// All variants this variant inherits from, nearest first. Resolved by the top-level
+    /// context once all variants have been declared.
+    pub ancestors: Vec<Ident>,
+}
+
+impl VariantContext {
+    fn parse_option(&mut self, meta: Meta) -> Result<()> {
+        match &meta {
+            Meta::NameValue(meta) if meta.path.is_ident("vis") => {
+                self.vis = Some(lit_str(meta)?.parse()?);
+            }
+            Meta::NameValue(meta) if meta.path.is_ident("repr") => {
+                self.repr = Some(lit_str(meta)?.parse()?);
+            }
+            Meta::NameValue(meta) if meta.path.is_ident("validate_error") => {
+                self.validate_error = Some(lit_str(meta)?.parse()?);
+            }
+            Meta::NameValue(meta) if meta.path.is_ident("default") => {
+                self.default = Some(parse2(meta.value.to_token_stream())?);
+            }
+            Meta::Path(path) if path.is_ident("kind") => self.kind = true,
+            Meta::Path(path) if path.is_ident("from_base") => self.from_base = true,
+            Meta::Path(path) if path.is_ident("borrowed") => self.borrowed = true,
+            Meta::Path(path) if path.is_ident("patch") => self.patch = true,
+            Meta::Path(path) if path.is_ident("diff") => self.diff = true,
+            Meta::Path(path) if path.is_ident("complete") => self.complete = true,
+            Meta::Path(path) if path.is_ident("impl_default") => self.impl_default = true,
+            Meta::Path(path) if path.is_ident("builder") => self.builder = true,
+            Meta::List(meta) if meta.path.is_ident("borrowed") => {
+                self.borrowed = true;
+                self.accessor = Some(meta.parse_args()?);
+            }
+            Meta::List(meta) if meta.path.is_ident("rename") => {
+                for rename in
+                    meta.parse_args_with(Punctuated::<Rename, Token![,]>::parse_terminated)?
+                {
+                    if self
+                        .renames
+                        .insert(rename.case.clone(), rename.name)
+                        .is_some()
+                    {
+                        return Err(Error::new(
+                            rename.case.span(),
+                            format!("duplicate rename of case `{}`", rename.case),
+                        ));
+                    }
+                }
+            }
+            _ => self.attrs.push(parse_quote!(#[#meta])),
+        }
+
+        Ok(())
+    }
+}
+
+impl Parse for VariantContext {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut variant_ctx = Self {
+            ident: input.parse()?,
+            attrs: Vec::new(),
+            vis: None,
+            repr: None,
+            default: None,
+            renames: HashMap::new(),
+            kind: false,
+            from_base: false,
+            borrowed: false,
+            accessor: None,
+            patch: false,
+            diff: false,
+            complete: false,
+            impl_default: false,
+            builder: false,
+            validate_error: None,
+            parents: Vec::new(),
+            ancestors: Vec::new(),
+        };
+
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+
+            for meta in Punctuated::<Meta, Token![,]>::parse_terminated(&content)? {
+                variant_ctx.parse_option(meta)?;
+            }
+        }
+
+        if variant_ctx.from_base && variant_ctx.borrowed {
+            return Err(Error::new(
+                variant_ctx.ident.span(),
+                format!(
+                    "variant `{}` cannot be both `from_base` and `borrowed`",
+                    variant_ctx.ident
+                ),
+            ));
+        }
+
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+
+            loop {
+                variant_ctx.parents.push(input.parse()?);
+
+                if !input.peek(Token![+]) {
+                    break;
+                }
+
+                input.parse::<Token![+]>()?;
+            }
+        }
+
+        Ok(variant_ctx)
+    }
+}
+
+/// The renaming of an enum case, as in `Old = New`.
+///
+struct Rename {
+    case: Ident,
+    name: Ident,
+}
+
+impl Parse for Rename {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let case = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        Ok(Self {
+            case,
+            name: input.parse()?,
+        })
+    }
+}
+
+fn lit_str(meta: &MetaNameValue) -> Result<&LitStr> {
+    match &meta.value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(lit),
+        value => Err(Error::new(
+            value.span(),
+            format!(
+                "expected a string literal for `{}`",
+                meta.path
+                    .get_ident()
+                    .map(|ident| ident.to_string())
+                    .unwrap_or_default()
+            ),
+        )),
+    }
+}
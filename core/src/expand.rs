@@ -5,7 +5,8 @@ use syn::{Item, parse2, visit_mut::VisitMut};
 use crate::{
     context::{self, item::ItemContext},
     expanders::{self, item::ItemExpander},
-    utilities::errors_ext::ErrorsExt,
+    generators::item::ItemGenerator,
+    utilities::{errors_ext::ErrorsExt, item_ext::ItemExt},
 };
 
 pub fn expand(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -35,7 +36,7 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> TokenStream {
      */
 
     let mut expanded_item = item.clone();
-    let expansion_ctx = expanders::Context::new(None);
+    let expansion_ctx = expanders::Context::new(&ctx, None);
     let mut item_expander = ItemExpander::new(&expansion_ctx, &item_ctx);
     item_expander.visit_item_mut(&mut expanded_item);
 
@@ -52,10 +53,20 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     for variant in &ctx.variants {
         let mut expanded_item = item.clone();
-        let expansion_ctx = expanders::Context::new(Some(variant));
+        let expansion_ctx = expanders::Context::new(&ctx, Some(variant));
         let mut item_expander = ItemExpander::new(&expansion_ctx, &item_ctx);
         item_expander.visit_item_mut(&mut expanded_item);
 
+        if let Some(variant_ctx) = ctx.variant_ctxs.get(variant) {
+            if let Some(attrs) = expanded_item.attrs_mut() {
+                attrs.extend(variant_ctx.attrs.iter().cloned());
+            }
+
+            if let (Some(vis), Some(item_vis)) = (&variant_ctx.vis, expanded_item.vis_mut()) {
+                *item_vis = vis.clone();
+            }
+        }
+
         output.extend(quote! {
             #[automatically_derived]
             #expanded_item
@@ -66,6 +77,23 @@ pub fn expand(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     }
 
+    /* Generate the items that come along with the variants.
+     */
+
+    let mut item_generator = ItemGenerator::new(&ctx, &item_ctx);
+    item_generator.visit_item_mut(&mut item);
+
+    for generated_item in &item_generator.items {
+        output.extend(quote! {
+            #[automatically_derived]
+            #generated_item
+        });
+    }
+
+    if let Some(error) = item_generator.errors.combine() {
+        output.extend(error.into_compile_error());
+    }
+
     output
 }
 
@@ -373,6 +401,2122 @@ mod tests {
         assess_expansion_duration(time_start, time_end, 2000);
     }
 
+    #[test]
+    fn expand_enum_base_placeholders() {
+        let attr = quote! {
+            Bar
+        };
+
+        let input = quote! {
+            enum Foo {
+                Node {
+                    #[variants(include(Bar), retype = "Option<Box<{b}{v}>>")]
+                    next: Box<Foo>,
+
+                    #[variants(include(Bar), retype = "Vec<{b}>")]
+                    others: Vec<Foo>,
+                },
+            }
+        };
+
+        // `{b}` is the enum, not the case holding the field.
+        let expect = quote! {
+            enum Foo {
+                Node {
+                    next: Box<Foo>,
+                    others: Vec<Foo>,
+                },
+            }
+            #[automatically_derived]
+            enum FooBar {
+                Node {
+                    next: Option<Box<FooBar>>,
+                    others: Vec<Foo>,
+                },
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_naming() {
+        let attr = quote! {
+            Bar, Baz as Lite, name = "{v}Of{b}"
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Bar, Baz), retype = "Option<Box<{b}{v}>>")]
+                recurse: Option<Box<Foo>>,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                recurse: Option<Box<Foo>>,
+            }
+            #[automatically_derived]
+            struct BarOfFoo {
+                recurse: Option<Box<BarOfFoo>>,
+            }
+            #[automatically_derived]
+            struct Lite {
+                recurse: Option<Box<Lite>>,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Bar, name = "{v}{b}"
+        };
+
+        let input = quote! {
+            impl Hello for Vec<base!(Foo)> {
+                fn hello() -> String {
+                    let _: ty!();
+
+                    #[variants(include(Bar), vary_type)]
+                    Foo {};
+
+                    type_str!().into()
+                }
+            }
+        };
+
+        let expect = quote! {
+            impl Hello for Vec<Foo> {
+                fn hello() -> String {
+                    let _: Foo;
+                    Foo {};
+                    "Foo".into()
+                }
+            }
+            #[automatically_derived]
+            impl Hello for Vec<BarFoo> {
+                fn hello() -> String {
+                    let _: BarFoo;
+                    BarFoo {};
+                    "BarFoo".into()
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_variant_attributes() {
+        let attr = quote! {
+            Bar(derive(Debug)), Baz(derive(Clone, Default), doc = "Baz")
+        };
+
+        let input = quote! {
+            #[derive(PartialEq)]
+            struct Foo {
+                #[variants(include(Bar, Baz))]
+                bar: usize,
+            }
+        };
+
+        let expect = quote! {
+            #[derive(PartialEq)]
+            struct Foo {
+                bar: usize,
+            }
+            #[automatically_derived]
+            #[derive(PartialEq)]
+            #[derive(Debug)]
+            struct FooBar {
+                bar: usize,
+            }
+            #[automatically_derived]
+            #[derive(PartialEq)]
+            #[derive(Clone, Default)]
+            #[doc = "Baz"]
+            struct FooBaz {
+                bar: usize,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_variant_visibility() {
+        let attr = quote! {
+            Bar(vis = "pub"), Baz(vis = "pub(crate)")
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Bar), vis = "pub")]
+                #[variants(include(Baz))]
+                bar: usize,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                bar: usize,
+            }
+            #[automatically_derived]
+            pub struct FooBar {
+                pub bar: usize,
+            }
+            #[automatically_derived]
+            pub(crate) struct FooBaz {
+                bar: usize,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_enum_field_visibility() {
+        let attr = quote! {
+            Bar
+        };
+
+        let input = quote! {
+            enum Foo {
+                Case(#[variants(include(Bar), vis = "pub")] usize),
+            }
+        };
+
+        assert!(
+            expand(attr, input)
+                .to_string()
+                .contains("`vis` cannot be set on the fields of enum cases")
+        );
+    }
+
+    #[test]
+    fn expand_variant_inheritance() {
+        let attr = quote! {
+            Summary, Detail: Summary, Full: Detail
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Summary))]
+                id: usize,
+
+                #[variants(include(Detail), retype = "Option<{}>")]
+                name: String,
+
+                #[variants(include(Full))]
+                #[variants(include(Summary), retype = "Box<{}>")]
+                description: String,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: usize,
+                name: String,
+                description: String,
+            }
+            #[automatically_derived]
+            struct FooSummary {
+                id: usize,
+                description: Box<String>,
+            }
+            #[automatically_derived]
+            struct FooDetail {
+                id: usize,
+                name: Option<String>,
+                description: Box<String>,
+            }
+            #[automatically_derived]
+            struct FooFull {
+                id: usize,
+                name: Option<String>,
+                description: String,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Summary(default = include), Detail: Summary, Full(default = exclude): Detail
+        };
+
+        let input = quote! {
+            struct Foo {
+                id: usize,
+                #[variants(include(Full))]
+                name: String,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: usize,
+                name: String,
+            }
+            #[automatically_derived]
+            struct FooSummary {
+                id: usize,
+                name: String,
+            }
+            #[automatically_derived]
+            struct FooDetail {
+                id: usize,
+                name: String,
+            }
+            #[automatically_derived]
+            struct FooFull {
+                name: String,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Summary: Full, Detail: Summary, Full: Detail
+        };
+
+        let input = quote! {
+            struct Foo;
+        };
+
+        assert!(
+            expand(attr, input)
+                .to_string()
+                .contains("variant `Summary` inherits from itself")
+        );
+    }
+
+    #[test]
+    fn expand_field_exclusion() {
+        let attr = quote! {
+            Bar, Baz, Bat
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(exclude(Bar))]
+                bar: usize,
+
+                #[variants(exclude(Bar), retype = "Option<{}>")]
+                #[variants(include(Baz), retype = "Box<{}>")]
+                baz: usize,
+            }
+        };
+
+        // The retype of an exclusion does not apply to the variants it implicitly includes.
+        let expect = quote! {
+            struct Foo {
+                bar: usize,
+                baz: usize,
+            }
+            #[automatically_derived]
+            struct FooBar {}
+            #[automatically_derived]
+            struct FooBaz {
+                bar: usize,
+                baz: Box<usize>,
+            }
+            #[automatically_derived]
+            struct FooBat {
+                bar: usize,
+                baz: usize,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Bar, Baz
+        };
+
+        let input = quote! {
+            enum Foo {
+                Struct {
+                    #[variants(exclude(Bar))]
+                    id: usize,
+                },
+                Tuple(#[variants(exclude(Baz))] u64),
+            }
+        };
+
+        let expect = quote! {
+            enum Foo {
+                Struct { id: usize },
+                Tuple(u64),
+            }
+            #[automatically_derived]
+            enum FooBar {
+                Struct {},
+                Tuple(u64),
+            }
+            #[automatically_derived]
+            enum FooBaz {
+                Struct { id: usize },
+                Tuple(),
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Bar, Baz, Bat
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(exclude(Bar))]
+                #[variants(exclude(Baz), retype = "Option<{}>")]
+                bar: usize,
+            }
+        };
+
+        assert!(
+            expand(attr.clone(), input)
+                .to_string()
+                .contains("conflicting settings for variant `Bat`")
+        );
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(*), exclude(Bar), retype = "Option<{}>")]
+                bar: usize,
+                #[variants(include(*), retype = "Option<{}>")]
+                #[variants(exclude(Bar))]
+                baz: usize,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                bar: usize,
+                baz: usize,
+            }
+            #[automatically_derived]
+            struct FooBar {}
+            #[automatically_derived]
+            struct FooBaz {
+                bar: Option<usize>,
+                baz: Option<usize>,
+            }
+            #[automatically_derived]
+            struct FooBat {
+                bar: Option<usize>,
+                baz: Option<usize>,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_default_inclusion() {
+        let attr = quote! {
+            Bar, Baz(default = exclude), default = include
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(exclude(Bar))]
+                bar: usize,
+                #[variants(include(Baz))]
+                baz: usize,
+                bat: usize,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                bar: usize,
+                baz: usize,
+                bat: usize,
+            }
+            #[automatically_derived]
+            struct FooBar {
+                baz: usize,
+                bat: usize,
+            }
+            #[automatically_derived]
+            struct FooBaz {
+                bar: usize,
+                baz: usize,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Bar(default = include)
+        };
+
+        let input = quote! {
+            impl Foo {
+                fn new() -> Self {
+                    Self {
+                        #[variants(exclude(Bar))]
+                        bar: 0,
+                        baz: 0,
+                    }
+                }
+            }
+        };
+
+        let expect = quote! {
+            impl Foo {
+                fn new() -> Self {
+                    Self { bar: 0, baz: 0 }
+                }
+            }
+            #[automatically_derived]
+            impl FooBar {
+                fn new() -> Self {
+                    Self { baz: 0 }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_selectors() {
+        let attr = quote! {
+            Summary, Detail, Update, group(Read = [Summary, Detail])
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(*))]
+                id: usize,
+
+                #[variants(include(Read))]
+                #[variants(include(Update), retype = "Option<{}>")]
+                name: String,
+
+                #[variants(exclude(Read))]
+                #[variants(include(Update), retype = "Option<{}>")]
+                description: String,
+
+                #[variants(include(*))]
+                #[variants(include(Update), retype = "Option<{}>")]
+                count: usize,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: usize,
+                name: String,
+                description: String,
+                count: usize,
+            }
+            #[automatically_derived]
+            struct FooSummary {
+                id: usize,
+                name: String,
+                count: usize,
+            }
+            #[automatically_derived]
+            struct FooDetail {
+                id: usize,
+                name: String,
+                count: usize,
+            }
+            #[automatically_derived]
+            struct FooUpdate {
+                id: usize,
+                name: Option<String>,
+                description: Option<String>,
+                count: Option<usize>,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Summary, Update, group(Write = [Update])
+        };
+
+        let input = quote! {
+            impl Foo {
+                fn new() -> Self {
+                    #[variants(exclude(Write))]
+                    let _ = "Not in FooUpdate";
+
+                    Self {
+                        #[variants(include(*))]
+                        id: 0,
+                        #[variants(include(Write))]
+                        description: "".into(),
+                    }
+                }
+            }
+        };
+
+        let expect = quote! {
+            impl Foo {
+                fn new() -> Self {
+                    let _ = "Not in FooUpdate";
+                    Self {
+                        id: 0,
+                        description: "".into(),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl FooSummary {
+                fn new() -> Self {
+                    let _ = "Not in FooUpdate";
+                    Self { id: 0 }
+                }
+            }
+            #[automatically_derived]
+            impl FooUpdate {
+                fn new() -> Self {
+                    Self {
+                        id: 0,
+                        description: "".into(),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_enum_cases() {
+        let attr = quote! {
+            Public, Internal
+        };
+
+        let input = quote! {
+            enum Foo {
+                Created {
+                    #[variants(include(*))]
+                    id: usize,
+                },
+                #[variants(include(Internal))]
+                Debug(#[variants(include(Internal))] String),
+                #[variants(exclude(Public))]
+                Admin,
+                Deleted,
+            }
+        };
+
+        let expect = quote! {
+            enum Foo {
+                Created { id: usize },
+                Debug(String),
+                Admin,
+                Deleted,
+            }
+            #[automatically_derived]
+            enum FooPublic {
+                Created { id: usize },
+                Deleted
+            }
+            #[automatically_derived]
+            enum FooInternal {
+                Created { id: usize },
+                Debug(String),
+                Admin,
+                Deleted
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_case_renames() {
+        let attr = quote! {
+            V1, V2(rename(Removed = Deleted)), default = include
+        };
+
+        let input = quote! {
+            enum Foo {
+                #[variants(include(V2), rename = "Created")]
+                New { id: usize },
+                Removed(usize),
+            }
+        };
+
+        let expect = quote! {
+            enum Foo {
+                New { id: usize },
+                Removed(usize),
+            }
+            #[automatically_derived]
+            enum FooV1 {
+                Removed(usize),
+            }
+            #[automatically_derived]
+            enum FooV2 {
+                Created { id: usize },
+                Deleted(usize),
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            impl Foo {
+                fn id(&self) -> usize {
+                    match self {
+                        Self::Removed(id) => *id,
+                    }
+                }
+
+                fn removed(id: usize) -> Self {
+                    Self::Removed(id)
+                }
+
+                fn latest(id: usize) -> FooV2 {
+                    FooV2::Removed(id)
+                }
+            }
+        };
+
+        let expect = quote! {
+            impl Foo {
+                fn id(&self) -> usize {
+                    match self {
+                        Self::Removed(id) => *id,
+                    }
+                }
+
+                fn removed(id: usize) -> Self {
+                    Self::Removed(id)
+                }
+
+                fn latest(id: usize) -> FooV2 {
+                    FooV2::Deleted(id)
+                }
+            }
+            #[automatically_derived]
+            impl FooV1 {
+                fn id(&self) -> usize {
+                    match self {
+                        Self::Removed(id) => *id,
+                    }
+                }
+
+                fn removed(id: usize) -> Self {
+                    Self::Removed(id)
+                }
+
+                fn latest(id: usize) -> FooV2 {
+                    FooV2::Deleted(id)
+                }
+            }
+            #[automatically_derived]
+            impl FooV2 {
+                fn id(&self) -> usize {
+                    match self {
+                        Self::Deleted(id) => *id,
+                    }
+                }
+
+                fn removed(id: usize) -> Self {
+                    Self::Deleted(id)
+                }
+
+                fn latest(id: usize) -> FooV2 {
+                    FooV2::Deleted(id)
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            enum Foo {
+                #[variants(include(V1, V2), rename = "Gone")]
+                Removed(usize),
+            }
+        };
+
+        assert!(expand(attr, input).to_string().contains(
+            "case `Removed` is renamed to `Deleted` by the `rename(..)` option of variant `V2`"
+        ));
+    }
+
+    #[test]
+    fn expand_case_payloads() {
+        let attr = quote! {
+            V1, V2, default = include
+        };
+
+        let input = quote! {
+            enum Foo {
+                #[variants(include(V1), payload = "(u64, String)")]
+                #[variants(include(V2), payload = "{ id: u64, parent: Option<Box<{b}{v}>> }")]
+                Created { id: u64, name: String },
+                #[variants(include(V1))]
+                #[variants(include(V2), payload = "")]
+                Deleted(u64),
+            }
+        };
+
+        let expect = quote! {
+            enum Foo {
+                Created { id: u64, name: String },
+                Deleted(u64),
+            }
+            #[automatically_derived]
+            enum FooV1 {
+                Created(u64, String),
+                Deleted(u64),
+            }
+            #[automatically_derived]
+            enum FooV2 {
+                Created { id: u64, parent: Option<Box<FooV2>> },
+                Deleted,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_collapse_empty() {
+        let attr = quote! {
+            Bar, collapse_empty
+        };
+
+        let input = quote! {
+            struct Foo {
+                bar: usize,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                bar: usize,
+            }
+            #[automatically_derived]
+            struct FooBar;
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            enum Foo {
+                Created { id: usize },
+                Deleted(usize),
+            }
+        };
+
+        let expect = quote! {
+            enum Foo {
+                Created { id: usize },
+                Deleted(usize),
+            }
+            #[automatically_derived]
+            enum FooBar {
+                Created,
+                Deleted,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            impl Foo {
+                const HANDLER: fn() -> usize = || 0;
+
+                fn new(bar: usize) -> Self {
+                    Self { bar }
+                }
+
+                fn deleted() -> Self {
+                    match Self::new(0) {
+                        Self::Deleted() => Self::Deleted(),
+                        foo => foo,
+                    }
+                }
+
+                fn cases() -> [Self; 2] {
+                    [Self::A(), Self::HTTP()]
+                }
+
+                fn unrelated(&self) -> usize {
+                    let _ = Other {};
+                    HANDLER() + Self::HANDLER() + Self::len()
+                }
+            }
+        };
+
+        let expect = quote! {
+            impl Foo {
+                const HANDLER: fn() -> usize = || 0;
+
+                fn new(bar: usize) -> Self {
+                    Self { bar }
+                }
+
+                fn deleted() -> Self {
+                    match Self::new(0) {
+                        Self::Deleted() => Self::Deleted(),
+                        foo => foo,
+                    }
+                }
+
+                fn cases() -> [Self; 2] {
+                    [Self::A(), Self::HTTP()]
+                }
+
+                fn unrelated(&self) -> usize {
+                    let _ = Other {};
+                    HANDLER() + Self::HANDLER() + Self::len()
+                }
+            }
+            #[automatically_derived]
+            impl FooBar {
+                const HANDLER: fn() -> usize = || 0;
+
+                fn new(bar: usize) -> Self {
+                    Self
+                }
+
+                fn deleted() -> Self {
+                    match Self::new(0) {
+                        Self::Deleted => Self::Deleted,
+                        foo => foo,
+                    }
+                }
+
+                fn cases() -> [Self; 2] {
+                    [Self::A, Self::HTTP]
+                }
+
+                fn unrelated(&self) -> usize {
+                    let _ = Other {};
+                    HANDLER() + Self::HANDLER() + Self::len()
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_kind() {
+        let attr = quote! {
+            Public, Kind(kind, derive(Clone, Copy))
+        };
+
+        let input = quote! {
+            #[repr(u8)]
+            enum Foo<T> {
+                Created { id: T } = 1,
+                #[variants(include(Kind))]
+                Debug(String) = 2,
+                Deleted = 3,
+            }
+        };
+
+        let expect = quote! {
+            #[repr(u8)]
+            enum Foo<T> {
+                Created { id: T } = 1,
+                Debug(String) = 2,
+                Deleted = 3,
+            }
+            #[automatically_derived]
+            #[repr(u8)]
+            enum FooPublic<T> {
+                Created {} = 1,
+                Deleted = 3,
+            }
+            #[automatically_derived]
+            #[repr(u8)]
+            #[derive(Clone, Copy)]
+            enum FooKind {
+                Created = 1,
+                Debug = 2,
+                Deleted = 3,
+            }
+            #[automatically_derived]
+            impl<T> From<&Foo<T>> for FooKind {
+                fn from(value: &Foo<T>) -> Self {
+                    match value {
+                        Foo::Created { .. } => Self::Created,
+                        Foo::Debug { .. } => Self::Debug,
+                        Foo::Deleted { .. } => Self::Deleted,
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl<T> From<&FooPublic<T>> for FooKind {
+                fn from(value: &FooPublic<T>) -> Self {
+                    match value {
+                        FooPublic::Created { .. } => Self::Created,
+                        FooPublic::Deleted { .. } => Self::Deleted,
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            enum Foo {
+                #[variants(include(Kind), payload = "(u8)")]
+                Created { id: u64 },
+            }
+        };
+
+        assert!(
+            expand(attr, input)
+                .to_string()
+                .contains("the cases of kind variant `Kind` cannot have a payload")
+        );
+    }
+
+    #[test]
+    fn expand_repr_and_discriminants() {
+        let attr = quote! {
+            Ffi(repr = "u8"), default = include
+        };
+
+        let input = quote! {
+            #[derive(Clone, Copy)]
+            #[repr(u32)]
+            enum Foo {
+                Created = 1,
+                #[variants(exclude(Ffi))]
+                Debug = 2,
+                #[variants(include(Ffi), discriminant = 2)]
+                Deleted = 3,
+            }
+        };
+
+        let expect = quote! {
+            #[derive(Clone, Copy)]
+            #[repr(u32)]
+            enum Foo {
+                Created = 1,
+                Debug = 2,
+                Deleted = 3,
+            }
+            #[automatically_derived]
+            #[derive(Clone, Copy)]
+            #[repr(u8)]
+            enum FooFfi {
+                Created = 1,
+                Deleted = 2,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Ffi(repr = "C"), default = include
+        };
+
+        let input = quote! {
+            #[repr(Rust)]
+            struct Foo {
+                id: u32,
+            }
+        };
+
+        let expect = quote! {
+            #[repr(Rust)]
+            struct Foo {
+                id: u32,
+            }
+            #[automatically_derived]
+            #[repr(C)]
+            struct FooFfi {
+                id: u32,
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_split_cases() {
+        let attr = quote! {
+            Summary, split_cases
+        };
+
+        let input = quote! {
+            #[derive(Debug)]
+            pub enum Foo<T, U> {
+                Create {
+                    #[variants(include(Summary))]
+                    id: T,
+                    name: String,
+                },
+                Debug(U),
+                #[variants(exclude(Summary))]
+                Delete,
+            }
+        };
+
+        let expect = quote! {
+            #[derive(Debug)]
+            pub enum Foo<T, U> {
+                Create { id: T, name: String },
+                Debug(U),
+                Delete,
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            pub enum FooSummary<T, U> {
+                Create { id: T },
+                Debug(),
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            pub struct FooCreate<T> {
+                pub id: T,
+                pub name: String,
+            }
+            #[automatically_derived]
+            impl<T, U> From<FooCreate<T>> for Foo<T, U> {
+                fn from(FooCreate { id: id, name: name, }: FooCreate<T>) -> Self {
+                    Self::Create { id: id, name: name, }
+                }
+            }
+            #[automatically_derived]
+            impl<T, U> TryFrom<Foo<T, U>> for FooCreate<T> {
+                type Error = Foo<T, U>;
+
+                fn try_from(value: Foo<T, U>) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        Foo::Create { id: id, name: name, } => Ok(Self { id: id, name: name, }),
+                        value => Err(value),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            pub struct FooDebug<U>(pub U);
+            #[automatically_derived]
+            impl<T, U> From<FooDebug<U>> for Foo<T, U> {
+                fn from(FooDebug { 0: field_0, }: FooDebug<U>) -> Self {
+                    Self::Debug { 0: field_0, }
+                }
+            }
+            #[automatically_derived]
+            impl<T, U> TryFrom<Foo<T, U>> for FooDebug<U> {
+                type Error = Foo<T, U>;
+
+                fn try_from(value: Foo<T, U>) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        Foo::Debug { 0: field_0, } => Ok(Self { 0: field_0, }),
+                        value => Err(value),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            pub struct FooDelete;
+            #[automatically_derived]
+            impl<T, U> From<FooDelete> for Foo<T, U> {
+                fn from(FooDelete {}: FooDelete) -> Self {
+                    Self::Delete {}
+                }
+            }
+            #[automatically_derived]
+            impl<T, U> TryFrom<Foo<T, U>> for FooDelete {
+                type Error = Foo<T, U>;
+
+                fn try_from(value: Foo<T, U>) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        Foo::Delete {} => Ok(Self {}),
+                        value => Err(value),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            pub struct FooSummaryCreate<T> {
+                pub id: T,
+            }
+            #[automatically_derived]
+            impl<T, U> From<FooSummaryCreate<T>> for FooSummary<T, U> {
+                fn from(FooSummaryCreate { id: id, }: FooSummaryCreate<T>) -> Self {
+                    Self::Create { id: id, }
+                }
+            }
+            #[automatically_derived]
+            impl<T, U> TryFrom<FooSummary<T, U>> for FooSummaryCreate<T> {
+                type Error = FooSummary<T, U>;
+
+                fn try_from(value: FooSummary<T, U>) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        FooSummary::Create { id: id, } => Ok(Self { id: id, }),
+                        value => Err(value),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            pub struct FooSummaryDebug();
+            #[automatically_derived]
+            impl<T, U> From<FooSummaryDebug> for FooSummary<T, U> {
+                fn from(FooSummaryDebug {}: FooSummaryDebug) -> Self {
+                    Self::Debug {}
+                }
+            }
+            #[automatically_derived]
+            impl<T, U> TryFrom<FooSummary<T, U>> for FooSummaryDebug {
+                type Error = FooSummary<T, U>;
+
+                fn try_from(value: FooSummary<T, U>) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        FooSummary::Debug {} => Ok(Self {}),
+                        value => Err(value),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Summary, name = "{v}Of{b}", split_cases
+        };
+
+        let input = quote! {
+            #[derive(Debug)]
+            #[serde(tag = "type")]
+            enum Foo {
+                Ping(u8),
+            }
+        };
+
+        let expect = quote! {
+            #[derive(Debug)]
+            #[serde(tag = "type")]
+            enum Foo {
+                Ping(u8),
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            #[serde(tag = "type")]
+            enum SummaryOfFoo {
+                Ping(),
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            struct PingOfFoo(u8);
+            #[automatically_derived]
+            impl From<PingOfFoo> for Foo {
+                fn from(PingOfFoo { 0: field_0, }: PingOfFoo) -> Self {
+                    Self::Ping { 0: field_0, }
+                }
+            }
+            #[automatically_derived]
+            impl TryFrom<Foo> for PingOfFoo {
+                type Error = Foo;
+
+                fn try_from(value: Foo) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        Foo::Ping { 0: field_0, } => Ok(Self { 0: field_0, }),
+                        value => Err(value),
+                    }
+                }
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            struct PingOfSummaryOfFoo();
+            #[automatically_derived]
+            impl From<PingOfSummaryOfFoo> for SummaryOfFoo {
+                fn from(PingOfSummaryOfFoo {}: PingOfSummaryOfFoo) -> Self {
+                    Self::Ping {}
+                }
+            }
+            #[automatically_derived]
+            impl TryFrom<SummaryOfFoo> for PingOfSummaryOfFoo {
+                type Error = SummaryOfFoo;
+
+                fn try_from(value: SummaryOfFoo) -> Result<Self, Self::Error> {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        SummaryOfFoo::Ping {} => Ok(Self {}),
+                        value => Err(value),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Create, split_cases
+        };
+
+        let input = quote! {
+            enum Foo {
+                Create(u8),
+            }
+        };
+
+        assert!(expand(attr, input).to_string().contains(
+            "cannot split the case `Create` of `Foo` into `FooCreate`, which is already the name \
+             of another item"
+        ));
+    }
+
+    #[test]
+    fn expand_from_base() {
+        let attr = quote! {
+            Summary(from_base)
+        };
+
+        let input = quote! {
+            struct Foo(
+                #[variants(include(Summary))]
+                usize,
+                String,
+                #[variants(include(Summary), retype = "u64")]
+                u32,
+                #[variants(include(Summary), retype = "String", convert = mask)]
+                String,
+                #[variants(include(Summary), retype = "u64", project = hash)]
+                u32,
+            );
+        };
+
+        let expect = quote! {
+            struct Foo(usize, String, u32, String, u32);
+            #[automatically_derived]
+            struct FooSummary(usize, u64, String, u64);
+            #[automatically_derived]
+            impl From<Foo> for FooSummary {
+                fn from(value: Foo) -> Self {
+                    Self {
+                        0: value.0,
+                        1: ::core::convert::Into::into(value.2),
+                        2: mask(value.3),
+                        3: ::core::convert::Into::into(hash(value.4)),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_project() {
+        let attr = quote! {
+            Public(from_base), Internal(from_base)
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Public, Internal), retype = "u64", project = hash)]
+                id: u32,
+                #[variants(include(Public), project = mask)]
+                #[variants(include(Internal))]
+                email: String,
+                #[variants(include(Public), retype = "Box<str>", project = truncate, convert = shorten)]
+                description: String,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: u32,
+                email: String,
+                description: String,
+            }
+            #[automatically_derived]
+            struct FooPublic {
+                id: u64,
+                email: String,
+                description: Box<str>,
+            }
+            #[automatically_derived]
+            struct FooInternal {
+                id: u64,
+                email: String,
+            }
+            #[automatically_derived]
+            impl From<Foo> for FooPublic {
+                fn from(value: Foo) -> Self {
+                    Self {
+                        id: ::core::convert::Into::into(hash(value.id)),
+                        email: mask(value.email),
+                        description: shorten(truncate(value.description)),
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl From<Foo> for FooInternal {
+                fn from(value: Foo) -> Self {
+                    Self {
+                        id: ::core::convert::Into::into(hash(value.id)),
+                        email: value.email,
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            View(borrowed(as_view))
+        };
+
+        let input = quote! {
+            pub struct Foo {
+                #[variants(include(View), project = mask)]
+                email: String,
+            }
+        };
+
+        assert!(
+            expand(attr, input)
+                .to_string()
+                .contains("the fields of borrowed variant `View` cannot be projected")
+        );
+    }
+
+    #[test]
+    fn expand_borrowed() {
+        let attr = quote! {
+            View(borrowed(as_view)), default = include
+        };
+
+        let input = quote! {
+            pub struct Foo<T> {
+                id: T,
+                #[variants(include(View), retype = "str")]
+                name: String,
+                #[variants(include(View), retype = "Option<Box<{b}{v}<T>>>")]
+                parent: Option<Box<Foo<T>>>,
+            }
+        };
+
+        let expect = quote! {
+            pub struct Foo<T> {
+                id: T,
+                name: String,
+                parent: Option<Box<Foo<T>>>,
+            }
+            #[automatically_derived]
+            pub struct FooView<'__variants, T> {
+                id: &'__variants T,
+                name: &'__variants str,
+                parent: &'__variants Option<Box<FooView<'__variants, T>>>,
+            }
+            #[automatically_derived]
+            impl<'__variants, T> Foo<T> {
+                pub fn as_view(&'__variants self) -> FooView<'__variants, T> {
+                    FooView {
+                        id: &self.id,
+                        name: &self.name,
+                        parent: &self.parent,
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            impl<T> Foo<T> {
+                fn id(&self) -> &T {
+                    &self.id
+                }
+            }
+        };
+
+        let expect = quote! {
+            impl<T> Foo<T> {
+                fn id(&self) -> &T {
+                    &self.id
+                }
+            }
+            #[automatically_derived]
+            impl<T> FooView<'_, T> {
+                fn id(&self) -> &T {
+                    &self.id
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+
+        let attr = quote! {
+            Static(borrowed(as_static)), default = include
+        };
+
+        let input = quote! {
+            pub struct Bar<'a> {
+                name: &'a str,
+            }
+        };
+
+        let expect = quote! {
+            pub struct Bar<'a> {
+                name: &'a str,
+            }
+            #[automatically_derived]
+            pub struct BarStatic<'__variants, 'a> {
+                name: &'__variants &'a str,
+            }
+            #[automatically_derived]
+            impl<'__variants, 'a> Bar<'a> {
+                pub fn as_static(&'__variants self) -> BarStatic<'__variants, 'a> {
+                    BarStatic {
+                        name: &self.name,
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_complete() {
+        let attr = quote! {
+            Create(complete, derive(Clone)), default = include
+        };
+
+        let input = quote! {
+            #[derive(Debug)]
+            pub struct Foo {
+                #[variants(exclude(Create), complete = 0)]
+                id: usize,
+                #[variants(include(Create), retype = "String")]
+                name: Box<str>,
+                #[variants(exclude(Create), complete = Vec::new())]
+                tags: Vec<String>,
+            }
+        };
+
+        let expect = quote! {
+            #[derive(Debug)]
+            pub struct Foo {
+                id: usize,
+                name: Box<str>,
+                tags: Vec<String>,
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            #[derive(Clone)]
+            pub struct FooCreate {
+                name: String,
+            }
+            #[automatically_derived]
+            #[derive(Debug)]
+            #[derive(Clone)]
+            pub struct FooCreateRest {
+                id: usize,
+                tags: Vec<String>,
+            }
+            #[automatically_derived]
+            impl FooCreate {
+                pub fn complete(self, rest: FooCreateRest) -> Foo {
+                    Foo {
+                        name: ::core::convert::Into::<Box<str> >::into(self.name),
+                        id: rest.id,
+                        tags: rest.tags,
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl From<FooCreate> for Foo {
+                fn from(value: FooCreate) -> Self {
+                    value.complete(FooCreateRest {
+                        id: 0,
+                        tags: Vec::new(),
+                    })
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            pub struct Foo {
+                #[variants(include(Create), retype = "Option<{}>")]
+                name: String,
+                #[variants(include(Create), retype = "Option<Box<{b}{v}>>")]
+                parent: Option<Box<Foo>>,
+            }
+        };
+
+        let output = expand(attr, input).to_string();
+
+        assert!(output.contains(
+            "cannot complete `FooCreate` into `Foo`, as the retype of the field `name` cannot be \
+             converted back"
+        ));
+        assert!(output.contains(
+            "cannot complete `FooCreate` into `Foo`, as the retype of the field `parent` cannot be \
+             converted back"
+        ));
+    }
+
+    #[test]
+    fn expand_patch() {
+        let attr = quote! {
+            Update(patch)
+        };
+
+        let input = quote! {
+            struct Foo {
+                id: usize,
+                #[variants(include(Update), retype = "Option<{}>")]
+                name: String,
+                #[variants(include(Update), retype = "Option<{}>")]
+                note: Option<String>,
+                #[variants(include(Update), retype = "Option<Box<{b}{v}>>")]
+                parent: Option<Box<Foo>>,
+                #[variants(include(Update), retype = "Option<Box<{b}{v}>>")]
+                child: Box<Foo>,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: usize,
+                name: String,
+                note: Option<String>,
+                parent: Option<Box<Foo>>,
+                child: Box<Foo>,
+            }
+            #[automatically_derived]
+            struct FooUpdate {
+                name: Option<String>,
+                note: Option<Option<String> >,
+                parent: Option<Box<FooUpdate> >,
+                child: Option<Box<FooUpdate> >,
+            }
+            #[automatically_derived]
+            impl FooUpdate {
+                fn apply(self, target: &mut Foo) {
+                    if let Some(value) = self.name {
+                        target.name = value;
+                    }
+                    if let Some(value) = self.note {
+                        target.note = value;
+                    }
+                    if let (Some(value), Some(target)) = (self.parent, (&mut target.parent).as_mut()) {
+                        (*value).apply(&mut **target)
+                    };
+                    if let Some(value) = self.child {
+                        (*value).apply(&mut **&mut target.child);
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Update), retype = "Option<Box<{}>>")]
+                name: String,
+            }
+        };
+
+        assert!(expand(attr, input).to_string().contains(
+            "cannot patch a field with this retype, expected `Option<{}>` or a retype with `{b}{v}`"
+        ));
+    }
+
+    #[test]
+    fn expand_diff() {
+        let attr = quote! {
+            Update(patch, diff)
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Update))]
+                id: usize,
+                #[variants(include(Update), retype = "Option<{}>")]
+                name: String,
+                #[variants(include(Update), retype = "Option<Box<{b}{v}>>")]
+                child: Box<Foo>,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: usize,
+                name: String,
+                child: Box<Foo>,
+            }
+            #[automatically_derived]
+            struct FooUpdate {
+                id: usize,
+                name: Option<String>,
+                child: Option<Box<FooUpdate> >,
+            }
+            #[automatically_derived]
+            impl FooUpdate {
+                fn apply(self, target: &mut Foo) {
+                    target.id = self.id;
+                    if let Some(value) = self.name {
+                        target.name = value;
+                    }
+                    if let Some(value) = self.child {
+                        (*value).apply(&mut **&mut target.child);
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl FooUpdate {
+                fn diff(old: &Foo, new: &Foo) -> Self {
+                    Self {
+                        id: new.id.clone(),
+                        name: (old.name != new.name).then(|| new.name.clone()),
+                        child: (old.child != new.child)
+                            .then(|| Box::new(FooUpdate::diff(&**&old.child, &**&new.child))),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(Update), retype = "Option<Box<{b}{v}>>")]
+                parent: Option<Box<Foo>>,
+            }
+        };
+
+        assert!(expand(attr, input).to_string().contains(
+            "cannot diff the field `parent`, as a nested patch cannot describe the changes between \
+             `None` and `Some`"
+        ));
+    }
+
+    #[test]
+    fn expand_derive_conversions() {
+        let attr = quote! {
+            Detail, Summary, Update, derive_conversions
+        };
+
+        let input = quote! {
+            struct Foo(
+                #[variants(include(Detail, Summary))]
+                usize,
+                #[variants(include(Detail))]
+                #[variants(include(Update), retype = "Option<{}>")]
+                String,
+                #[variants(include(Detail, Summary))]
+                bool,
+            );
+        };
+
+        let expect = quote! {
+            struct Foo(usize, String, bool);
+            #[automatically_derived]
+            struct FooDetail(usize, String, bool);
+            #[automatically_derived]
+            struct FooSummary(usize, bool);
+            #[automatically_derived]
+            struct FooUpdate(Option<String>);
+            #[automatically_derived]
+            impl From<FooDetail> for FooSummary {
+                fn from(value: FooDetail) -> Self {
+                    Self { 0: value.0, 1: value.2, }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input.clone()), &expect);
+
+        let attr = quote! {
+            Detail, Summary, Update, derive_conversions(Detail -> Update)
+        };
+
+        assert!(expand(attr, input).to_string().contains(
+            "cannot convert `FooDetail` into `FooUpdate`, as the field `1` has different types"
+        ));
+    }
+
+    #[test]
+    fn expand_versioned() {
+        let attr = quote! {
+            V1, V2, V3, versioned
+        };
+
+        let input = quote! {
+            struct Foo {
+                id: u32,
+                #[variants(until(V3))]
+                legacy: String,
+                #[variants(since(V2, default = 1))]
+                revision: u64,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo {
+                id: u32,
+                legacy: String,
+                revision: u64,
+            }
+            #[automatically_derived]
+            struct FooV1 {
+                id: u32,
+                legacy: String,
+            }
+            #[automatically_derived]
+            struct FooV2 {
+                id: u32,
+                legacy: String,
+                revision: u64,
+            }
+            #[automatically_derived]
+            struct FooV3 {
+                id: u32,
+                revision: u64,
+            }
+            #[automatically_derived]
+            impl From<FooV1> for FooV2 {
+                fn from(value: FooV1) -> Self {
+                    Self { id: value.id, legacy: value.legacy, revision: 1, }
+                }
+            }
+            #[automatically_derived]
+            impl From<FooV2> for FooV3 {
+                fn from(value: FooV2) -> Self {
+                    Self { id: value.id, revision: value.revision, }
+                }
+            }
+            #[automatically_derived]
+            impl FooV1 {
+                fn migrate_to_latest(self) -> FooV3 {
+                    FooV3::from(FooV2::from(self))
+                }
+            }
+            #[automatically_derived]
+            impl FooV2 {
+                fn migrate_to_latest(self) -> FooV3 {
+                    FooV3::from(self)
+                }
+            }
+            #[automatically_derived]
+            impl FooV3 {
+                fn migrate_to_latest(self) -> FooV3 {
+                    self
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input.clone()), &expect);
+
+        let attr = quote! {
+            V1, V2, V3, versioned, derive_conversions
+        };
+
+        let output = expand(attr, input.clone()).to_string();
+        let migration = quote!(impl From<FooV2> for FooV3).to_string();
+
+        assert_eq!(output.matches(&migration).count(), 1);
+        assert!(output.contains(&quote!(impl From<FooV2> for FooV1).to_string()));
+
+        let attr = quote! {
+            V1, V2, V3, versioned, derive_conversions(V2 -> V3)
+        };
+
+        assert!(expand(attr, input).to_string().contains(
+            "cannot convert `FooV2` into `FooV3`, as it is already converted by its migration"
+        ));
+
+        let attr = quote! {
+            V1, V2, versioned
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(since(default = 1))]
+                revision: u64,
+            }
+        };
+
+        assert!(
+            expand(attr, input)
+                .to_string()
+                .contains("`default` requires a version, as in `since(V2, default = ..)`")
+        );
+    }
+
+    #[test]
+    fn expand_item_kinds() {
+        let attr = quote! {
+            Update(patch, builder), Kind(kind), derive_conversions, split_cases
+        };
+
+        let input = quote! {
+            enum Foo {
+                Bar,
+            }
+        };
+
+        let output = expand(attr.clone(), input).to_string();
+
+        assert!(output.contains("patch variant `Update` requires a struct"));
+        assert!(output.contains("builder variant `Update` requires a struct"));
+        assert!(output.contains("`derive_conversions` requires a struct"));
+        assert!(!output.contains("requires an enum"));
+
+        let input = quote! {
+            struct Foo;
+        };
+
+        let output = expand(attr, input).to_string();
+
+        assert!(output.contains("kind variant `Kind` requires an enum"));
+        assert!(output.contains("`split_cases` requires an enum"));
+        assert!(!output.contains("requires a struct"));
+
+        let attr = quote! {
+            View(borrowed(as_view), patch, impl_default)
+        };
+
+        let input = quote! {
+            struct Foo {
+                #[variants(include(View))]
+                id: u32,
+            }
+        };
+
+        let output = expand(attr, input).to_string();
+
+        assert!(output.contains("variant `View` cannot be both `patch` and `borrowed`"));
+        assert!(output.contains("variant `View` cannot be both `impl_default` and `borrowed`"));
+    }
+
+    #[test]
+    fn expand_validate() {
+        let attr = quote! {
+            Create(validate_error = "Invalid")
+        };
+
+        let input = quote! {
+            pub struct Foo {
+                #[variants(include(Create), validate = non_empty)]
+                name: String,
+                #[variants(include(Create))]
+                quantity: u32,
+            }
+        };
+
+        let expect = quote! {
+            pub struct Foo {
+                name: String,
+                quantity: u32,
+            }
+            #[automatically_derived]
+            pub struct FooCreate {
+                name: String,
+                quantity: u32,
+            }
+            #[automatically_derived]
+            impl FooCreate {
+                pub fn validate(&self) -> ::core::result::Result<(), ::std::vec::Vec<(&'static str, Invalid)>> {
+                    let mut errors = ::std::vec::Vec::new();
+
+                    if let ::core::result::Result::Err(error) = non_empty(&self.name) {
+                        errors.push(("name", error));
+                    }
+
+                    match errors.is_empty() {
+                        true => ::core::result::Result::Ok(()),
+                        false => ::core::result::Result::Err(errors),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            pub enum Foo {
+                Created {
+                    #[variants(include(Create), validate = non_empty)]
+                    name: String,
+                },
+            }
+        };
+
+        let expect = quote! {
+            pub enum Foo {
+                Created { name: String },
+            }
+            #[automatically_derived]
+            pub enum FooCreate {
+                Created { name: String },
+            }
+            #[automatically_derived]
+            impl FooCreate {
+                pub fn validate(&self) -> ::core::result::Result<(), ::std::vec::Vec<(&'static str, Invalid)>> {
+                    let mut errors = ::std::vec::Vec::new();
+
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        Self::Created { name: name, .. } => {
+                            if let ::core::result::Result::Err(error) = non_empty(name) {
+                                errors.push(("name", error));
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    match errors.is_empty() {
+                        true => ::core::result::Result::Ok(()),
+                        false => ::core::result::Result::Err(errors),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_impl_default() {
+        let attr = quote! {
+            Update(impl_default)
+        };
+
+        let input = quote! {
+            struct Foo<T> {
+                #[variants(include(Update), retype = "Option<{}>", default = Some(1))]
+                id: u32,
+                #[variants(include(Update))]
+                value: T,
+            }
+        };
+
+        let expect = quote! {
+            struct Foo<T> {
+                id: u32,
+                value: T,
+            }
+            #[automatically_derived]
+            struct FooUpdate<T> {
+                id: Option<u32>,
+                value: T,
+            }
+            #[automatically_derived]
+            impl<T> ::core::default::Default for FooUpdate<T>
+            where
+                T: ::core::default::Default,
+            {
+                fn default() -> Self {
+                    Self {
+                        id: Some(1),
+                        value: ::core::default::Default::default(),
+                    }
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr, input), &expect);
+    }
+
+    #[test]
+    fn expand_builder() {
+        let attr = quote! {
+            Summary(builder)
+        };
+
+        let input = quote! {
+            pub struct Foo(
+                #[variants(include(Summary), retype = "u64")]
+                u32,
+                #[variants(include(Summary), default = 0)]
+                u8,
+            );
+        };
+
+        let expect = quote! {
+            pub struct Foo(u32, u8);
+            #[automatically_derived]
+            pub struct FooSummary(u64, u8);
+            #[automatically_derived]
+            pub struct FooSummaryBuilder {
+                field_0: ::core::option::Option<u64>,
+                field_1: ::core::option::Option<u8>,
+            }
+            #[automatically_derived]
+            impl ::core::default::Default for FooSummaryBuilder {
+                fn default() -> Self {
+                    Self {
+                        field_0: ::core::option::Option::None,
+                        field_1: ::core::option::Option::None,
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl FooSummaryBuilder {
+                pub fn field_0(mut self, value: u64) -> Self {
+                    self.field_0 = ::core::option::Option::Some(value);
+                    self
+                }
+
+                pub fn field_1(mut self, value: u8) -> Self {
+                    self.field_1 = ::core::option::Option::Some(value);
+                    self
+                }
+
+                pub fn build(self) -> ::core::result::Result<FooSummary, &'static str> {
+                    ::core::result::Result::Ok(FooSummary {
+                        0: self.field_0.ok_or("field_0")?,
+                        1: self.field_1.unwrap_or_else(|| 0),
+                    })
+                }
+            }
+            #[automatically_derived]
+            impl FooSummary {
+                pub fn builder() -> FooSummaryBuilder {
+                    ::core::default::Default::default()
+                }
+            }
+        };
+
+        assert_eq_token_streams(&expand(attr.clone(), input), &expect);
+
+        let input = quote! {
+            pub struct Foo {
+                #[variants(include(Summary))]
+                build: u32,
+                #[variants(include(Summary))]
+                builder: u32,
+            }
+        };
+
+        let output = expand(attr, input).to_string();
+
+        assert!(output.contains(
+            "cannot generate the setter of the field `build` of `FooSummary`, which would collide \
+             with the `build` method"
+        ));
+        assert!(output.contains(
+            "cannot generate the setter of the field `builder` of `FooSummary`, which would \
+             collide with the `builder` method"
+        ));
+    }
+
     fn assert_eq_token_streams(a: &TokenStream, b: &TokenStream) {
         let a_str = a.to_string();
         let a_parsed = syn::parse_file(&a_str).unwrap();
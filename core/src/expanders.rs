@@ -1,6 +1,16 @@
-use syn::Ident;
+use syn::{
+    Attribute, Fields, GenericParam, Generics, Ident, Lifetime, LifetimeParam, Result, Visibility,
+    parse_quote,
+};
+
+use crate::{
+    context::{self, case::CaseContext, variant::VariantContext},
+    utilities::ident_ext::IdentExt,
+};
 
 pub mod block;
+pub mod case;
+pub mod collapse_empty;
 pub mod r#enum;
 pub mod expr_structs;
 pub mod field;
@@ -8,16 +18,134 @@ pub mod field_value;
 pub mod r#impl;
 pub mod item;
 pub mod macros;
+pub mod rename_case;
 pub mod stmt;
 pub mod r#struct;
 pub mod vary_type;
 
 pub struct Context<'a> {
+    pub ctx: &'a context::Context,
     pub variant: Option<&'a Ident>,
 }
 
 impl<'a> Context<'a> {
-    pub fn new(variant: Option<&'a Ident>) -> Self {
-        Self { variant }
+    pub fn new(ctx: &'a context::Context, variant: Option<&'a Ident>) -> Self {
+        Self { ctx, variant }
+    }
+
+    /// Create the identifier of the item generated from `base` for the current variant. When
+    /// expanding the base item, `base` is returned as is.
+    ///
+    pub fn variant_ident(&self, base: &Ident) -> Ident {
+        match self.variant {
+            Some(variant) => base.to_variant(&self.ctx.naming, variant),
+            None => base.clone(),
+        }
+    }
+
+    /// The options declared for the current variant, if any.
+    ///
+    pub fn variant_ctx(&self) -> Option<&'a VariantContext> {
+        self.ctx.variant_ctxs.get(self.variant?)
+    }
+
+    /// The visibility of the items generated for the current variant, from those of the base
+    /// item with visibility `base`.
+    ///
+    pub fn variant_vis(&self, base: &Visibility) -> Visibility {
+        self.variant_ctx()
+            .and_then(|variant_ctx| variant_ctx.vis.clone())
+            .unwrap_or(base.clone())
+    }
+
+    /// Replace any `#[repr(..)]` among the attributes `attrs` of a generated struct or enum by the
+    /// `repr` option of the current variant, if set.
+    ///
+    pub fn replace_repr(&self, attrs: &mut Vec<Attribute>) {
+        if let Some(repr) = self
+            .variant_ctx()
+            .and_then(|variant_ctx| variant_ctx.repr.as_ref())
+        {
+            attrs.retain(|attr| !attr.path().is_ident("repr"));
+            attrs.push(parse_quote!(#[repr(#repr)]));
+        }
+    }
+
+    /// Whether the current variant is a `kind` variant, whose enum cases have no fields.
+    ///
+    pub fn is_kind(&self) -> bool {
+        self.variant_ctx()
+            .is_some_and(|variant_ctx| variant_ctx.kind)
+    }
+
+    /// The lifetime of the fields of the current variant if it is `borrowed`. Its name is fixed,
+    /// rather than derived from the variant, such that it is never a keyword or a lifetime the
+    /// base already declares.
+    ///
+    pub fn borrow_lifetime(&self) -> Option<Lifetime> {
+        let variant = self.variant?;
+
+        if !self.variant_ctx()?.borrowed {
+            return None;
+        }
+
+        Some(Lifetime::new("'__variants", variant.span()))
+    }
+
+    /// Create the generics of the item generated from an item with the generics `base` for the
+    /// current variant: kinds have none, as they have no fields to use them in, and borrowed
+    /// variants have their lifetime added.
+    ///
+    pub fn variant_generics(&self, base: &Generics) -> Generics {
+        if self.is_kind() {
+            return Generics::default();
+        }
+
+        let mut generics = base.clone();
+
+        if let Some(lifetime) = self.borrow_lifetime() {
+            generics
+                .params
+                .insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+            generics.lt_token.get_or_insert_with(Default::default);
+            generics.gt_token.get_or_insert_with(Default::default);
+        }
+
+        generics
+    }
+
+    /// Whether `fields` are to be collapsed into a unit, which is the case for fields left empty
+    /// in a variant when the `collapse_empty` option is set.
+    ///
+    pub fn collapses(&self, fields: &Fields) -> bool {
+        self.ctx.collapse_empty && self.variant.is_some() && fields.is_empty()
+    }
+
+    /// The name of the enum case `case` in the current variant, if it is renamed: the `rename`
+    /// setting of the case in `case_ctx`, or otherwise the name declared in the top-level
+    /// `rename(..)` option of the variant. Impls are expanded without the enum, such that only
+    /// the latter applies to them.
+    ///
+    pub fn case_rename(
+        &self,
+        case: &Ident,
+        case_ctx: Option<&CaseContext>,
+    ) -> Result<Option<Ident>> {
+        let variant = match self.variant {
+            Some(variant) => variant,
+            None => return Ok(None),
+        };
+
+        let rename = case_ctx
+            .and_then(|case_ctx| case_ctx.settings.get(variant))
+            .and_then(|settings| settings.rename.as_ref());
+
+        match rename {
+            Some(rename) => rename.parse().map(Some),
+            None => Ok(self
+                .variant_ctx()
+                .and_then(|variant_ctx| variant_ctx.renames.get(case))
+                .cloned()),
+        }
     }
 }
@@ -0,0 +1,153 @@
+use syn::{
+    Error, Fields, Ident, LitStr, Result, Variant, parse::ParseStream, token, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{case::CaseContext, r#enum::EnumContext},
+    utilities::fields_ext::FieldsExt,
+};
+
+use super::{Context, field::FieldExpander};
+
+pub struct CaseExpander<'a> {
+    context: &'a Context<'a>,
+    enum_base_ident: &'a Ident,
+    enum_ctx: &'a EnumContext<'a>,
+    case_ctx: &'a CaseContext<'a>,
+    pub print_case: bool,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> CaseExpander<'a> {
+    /// Expands an enum case (an enum `Variant` node) and its fields. Cases without settings are
+    /// kept in every variant, whereas cases with settings are only kept in the variants that
+    /// include them. Cases are renamed by their own `rename` setting, or otherwise by the
+    /// `rename(..)` option of the variant, their discriminant is overridden by a `discriminant`
+    /// setting, and their fields are replaced entirely by a `payload` setting.
+    ///
+    pub fn new(
+        context: &'a Context,
+        enum_base_ident: &'a Ident,
+        enum_ctx: &'a EnumContext,
+        case_ctx: &'a CaseContext,
+    ) -> Self {
+        Self {
+            context,
+            enum_base_ident,
+            enum_ctx,
+            case_ctx,
+            print_case: true,
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl CaseExpander<'_> {
+    /// Parse the payload that replaces all fields of a case, such as `(u64, String)` or
+    /// `{ id: u64 }`, or nothing for a unit case. The `{b}`, `{v}` and `{b}{v}` placeholders are
+    /// replaced as they are in retypes.
+    ///
+    fn parse_payload(&self, payload: &LitStr, variant: &Ident) -> Result<Fields> {
+        let variant_ident = self.context.variant_ident(self.enum_base_ident);
+
+        let payload_str = payload
+            .value()
+            .replace("{b}{v}", &variant_ident.to_string())
+            .replace("{b}", &self.enum_base_ident.to_string())
+            .replace("{v}", &variant.to_string());
+
+        LitStr::new(&payload_str, payload.span()).parse_with(|input: ParseStream| {
+            if input.is_empty() {
+                Ok(Fields::Unit)
+            } else if input.peek(token::Paren) {
+                input.parse().map(Fields::Unnamed)
+            } else if input.peek(token::Brace) {
+                input.parse().map(Fields::Named)
+            } else {
+                Err(input.error("expected a payload such as `(T, U)` or `{ name: T }`"))
+            }
+        })
+    }
+}
+
+impl VisitMut for CaseExpander<'_> {
+    fn visit_variant_mut(&mut self, node: &mut Variant) {
+        if let Some(variant) = self.context.variant {
+            let settings = self.case_ctx.settings.get(variant);
+
+            self.print_case = match settings {
+                Some(settings) => settings.include,
+                None => self.case_ctx.settings.is_empty(),
+            };
+
+            if !self.print_case {
+                return;
+            }
+
+            match self.context.case_rename(&node.ident, Some(self.case_ctx)) {
+                Ok(Some(ident)) => node.ident = ident,
+                Ok(None) => {}
+                Err(error) => self.errors.push(error),
+            }
+
+            if let Some(discriminant) = settings.and_then(|settings| settings.discriminant.as_ref())
+            {
+                node.discriminant = Some((Default::default(), discriminant.clone()));
+            }
+
+            if let Some(payload) = settings.and_then(|settings| settings.payload.as_ref()) {
+                if self.context.is_kind() {
+                    self.errors.push(Error::new(
+                        payload.span(),
+                        format!(
+                            "the cases of kind variant `{}` cannot have a payload",
+                            variant
+                        ),
+                    ));
+                    node.fields = Fields::Unit;
+                    return;
+                }
+
+                match self.parse_payload(payload, variant) {
+                    Ok(fields) => node.fields = fields,
+                    Err(error) => self.errors.push(error),
+                }
+
+                return;
+            }
+
+            if self.context.is_kind() {
+                node.fields = Fields::Unit;
+                return;
+            }
+        }
+
+        let print_fields = node
+            .fields
+            .iter_mut()
+            .filter_map(|field| {
+                let field_ctx = match self.enum_ctx.field_ctxs.get(field) {
+                    Some(context) => context,
+                    None => return None,
+                };
+
+                let mut field_expander =
+                    FieldExpander::new(self.context, self.enum_base_ident, field_ctx);
+                field_expander.visit_field_mut(field);
+                self.errors.append(&mut field_expander.errors);
+
+                if field_expander.print_field {
+                    Some(field.clone())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        node.fields.replace_fields(print_fields);
+
+        if self.context.collapses(&node.fields) {
+            node.fields = Fields::Unit;
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use syn::{
+    Expr, ExprPath, Ident, ImplItem, ItemImpl, Pat, PatPath, Path,
+    visit_mut::{VisitMut, visit_expr_mut, visit_item_impl_mut, visit_pat_mut},
+};
+
+pub struct CollapseEmptyExpander<'a> {
+    variant_ident: &'a Ident,
+    associated: HashSet<Ident>,
+}
+
+impl<'a> CollapseEmptyExpander<'a> {
+    /// Rewrites struct expressions without fields, such as `Self {}`, and constructors without
+    /// arguments, such as `Self()` or `Self::Case()`, into their unit form, to match the structs
+    /// and enum cases collapsed by the `collapse_empty` option. Patterns are rewritten alike.
+    /// Only paths to `Self` or to the item `variant_ident` generated for the variant, and to
+    /// their enum cases, are rewritten.
+    ///
+    pub fn new(variant_ident: &'a Ident) -> Self {
+        Self {
+            variant_ident,
+            associated: HashSet::new(),
+        }
+    }
+
+    /// Whether `path` names the variant item itself, as in `Self` or `FooBar`.
+    ///
+    fn is_item(&self, path: &Path) -> bool {
+        path.segments
+            .last()
+            .is_some_and(|segment| self.is_item_ident(&segment.ident))
+    }
+
+    /// Whether `path` names an enum case of the variant item, as in `Self::Case`.
+    ///
+    fn is_case(&self, path: &Path) -> bool {
+        let len = path.segments.len();
+        len >= 2 && self.is_item_ident(&path.segments[len - 2].ident)
+    }
+
+    fn is_item_ident(&self, ident: &Ident) -> bool {
+        ident == "Self" || ident == self.variant_ident
+    }
+
+    /// Whether the called path `path`, such as `Self::Case`, names an enum case rather than an
+    /// associated function or constant. Impls are expanded without their enum, such that its
+    /// cases are not known: functions and constants declared by the impl are told apart by name,
+    /// and others by their snake case, as in `Self::new`.
+    ///
+    fn is_case_call(&self, path: &Path) -> bool {
+        path.segments.last().is_some_and(|segment| {
+            !self.associated.contains(&segment.ident)
+                && !segment
+                    .ident
+                    .to_string()
+                    .starts_with(|c: char| c.is_lowercase() || c == '_')
+        })
+    }
+}
+
+impl VisitMut for CollapseEmptyExpander<'_> {
+    fn visit_item_impl_mut(&mut self, node: &mut ItemImpl) {
+        self.associated = node
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Const(item) => Some(item.ident.clone()),
+                ImplItem::Fn(item) => Some(item.sig.ident.clone()),
+                _ => None,
+            })
+            .collect();
+
+        visit_item_impl_mut(self, node);
+    }
+
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        visit_expr_mut(self, node);
+
+        let path = match node {
+            Expr::Struct(expr)
+                if expr.fields.is_empty()
+                    && expr.rest.is_none()
+                    && (self.is_item(&expr.path) || self.is_case(&expr.path)) =>
+            {
+                expr.path.clone()
+            }
+            Expr::Call(expr) if expr.args.is_empty() => match expr.func.as_ref() {
+                Expr::Path(func)
+                    if func.qself.is_none()
+                        && (self.is_item(&func.path)
+                            || self.is_case(&func.path) && self.is_case_call(&func.path)) =>
+                {
+                    func.path.clone()
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+
+        *node = Expr::Path(ExprPath {
+            attrs: Vec::new(),
+            qself: None,
+            path,
+        });
+    }
+
+    fn visit_pat_mut(&mut self, node: &mut Pat) {
+        visit_pat_mut(self, node);
+
+        let path = match node {
+            Pat::TupleStruct(pat)
+                if pat.elems.is_empty() && (self.is_item(&pat.path) || self.is_case(&pat.path)) =>
+            {
+                pat.path.clone()
+            }
+            _ => return,
+        };
+
+        *node = Pat::Path(PatPath {
+            attrs: Vec::new(),
+            qself: None,
+            path,
+        });
+    }
+}
@@ -1,11 +1,8 @@
-use syn::{Error, ItemEnum, Variant, visit_mut::VisitMut};
+use syn::{Error, ItemEnum, visit_mut::VisitMut};
 
-use crate::{
-    context::r#enum::EnumContext,
-    utilities::{fields_ext::FieldsExt, ident_ext::IdentExt},
-};
+use crate::{context::r#enum::EnumContext, utilities::vec_ext::VecExt};
 
-use super::{Context, field::FieldExpander};
+use super::{Context, case::CaseExpander};
 
 pub struct EnumExpander<'a> {
     context: &'a Context<'a>,
@@ -25,37 +22,33 @@ impl<'a> EnumExpander<'a> {
 
 impl VisitMut for EnumExpander<'_> {
     fn visit_item_enum_mut(&mut self, node: &mut ItemEnum) {
-        for variant in &mut node.variants {
-            self.visit_variant_mut(variant);
-        }
-
-        if let Some(variant) = self.context.variant {
-            node.ident = node.ident.from_appendix(variant);
-        }
-    }
-
-    fn visit_variant_mut(&mut self, node: &mut Variant) {
-        let print_fields = node
-            .fields
+        let print_cases = node
+            .variants
             .iter_mut()
-            .filter_map(|field| {
-                let field_ctx = match self.enum_ctx.field_ctxs.get(field) {
+            .filter_map(|case| {
+                let case_ctx = match self.enum_ctx.case_ctxs.get(case) {
                     Some(context) => context,
                     None => return None,
                 };
 
-                let mut field_expander = FieldExpander::new(self.context, &node.ident, &field_ctx);
-                field_expander.visit_field_mut(field);
-                self.errors.append(&mut field_expander.errors);
+                let mut case_expander =
+                    CaseExpander::new(self.context, &node.ident, self.enum_ctx, case_ctx);
+                case_expander.visit_variant_mut(case);
+                self.errors.append(&mut case_expander.errors);
 
-                if field_expander.print_field {
-                    Some(field.clone())
+                if case_expander.print_case {
+                    Some(case.clone())
                 } else {
                     None
                 }
             })
             .collect::<Vec<_>>();
 
-        node.fields.replace_fields(print_fields);
+        node.variants = print_cases.into_punctuated();
+
+        node.generics = self.context.variant_generics(&node.generics);
+
+        self.context.replace_repr(&mut node.attrs);
+        node.ident = self.context.variant_ident(&node.ident);
     }
 }
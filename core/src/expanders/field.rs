@@ -1,7 +1,10 @@
 use quote::quote;
-use syn::{Error, Field, Ident, LitStr, Type, visit_mut::VisitMut};
+use syn::{
+    Error, Field, Ident, Lifetime, LitStr, PathArguments, Type, TypePath, Visibility, parse_quote,
+    visit_mut::{VisitMut, visit_type_path_mut},
+};
 
-use crate::context::field::FieldContext;
+use crate::context::field::{FieldContext, VariantSettings};
 
 use super::Context;
 
@@ -29,27 +32,19 @@ impl<'a> FieldExpander<'a> {
     }
 }
 
-impl VisitMut for FieldExpander<'_> {
-    fn visit_field_mut(&mut self, node: &mut Field) {
-        let variant = match self.context.variant {
-            Some(variant) => variant,
-            None => return,
-        };
-
-        let settings = match self.field_ctx.settings.get(variant) {
-            Some(settings) => settings,
-            None => {
-                self.print_field = false;
-                return;
-            }
-        };
-
+impl FieldExpander<'_> {
+    /// Apply the retype and visibility settings of the field for `variant`.
+    ///
+    fn apply_settings(&mut self, node: &mut Field, variant: &Ident, settings: &VariantSettings) {
         if let Some(retype) = &settings.retype {
             let ty = &node.ty;
             let ty_str = quote!(#ty).to_string();
 
+            let variant_ident = self.context.variant_ident(self.struct_base_ident);
+
             let retyped_str = retype
                 .value()
+                .replace("{b}{v}", &variant_ident.to_string())
                 .replace("{}", &ty_str)
                 .replace("{t}", &ty_str)
                 .replace("{b}", &self.struct_base_ident.to_string())
@@ -60,5 +55,81 @@ impl VisitMut for FieldExpander<'_> {
                 Err(error) => self.errors.push(error),
             }
         }
+
+        if let Some(vis) = &settings.vis {
+            match vis.parse::<Visibility>() {
+                Ok(vis) => node.vis = vis,
+                Err(error) => self.errors.push(error),
+            }
+        }
+    }
+}
+
+impl VisitMut for FieldExpander<'_> {
+    fn visit_field_mut(&mut self, node: &mut Field) {
+        let variant = match self.context.variant {
+            Some(variant) => variant,
+            None => return,
+        };
+
+        let settings = self.field_ctx.settings.get(variant);
+
+        self.print_field = match settings {
+            Some(settings) => settings.include,
+            None => self.context.ctx.includes_by_default(variant),
+        };
+
+        if !self.print_field {
+            return;
+        }
+
+        if let Some(settings) = settings {
+            self.apply_settings(node, variant, settings);
+        }
+
+        if let Some(lifetime) = self.context.borrow_lifetime() {
+            let variant_ident = self.context.variant_ident(self.struct_base_ident);
+            BorrowedVariantLifetime::new(&variant_ident, &lifetime).visit_type_mut(&mut node.ty);
+
+            let ty = &node.ty;
+            node.ty = parse_quote!(&#lifetime #ty);
+        }
+    }
+}
+
+/// Adds the lifetime of a borrowed variant to the nested uses of the variant in a field type,
+/// such as `FooView` in a field retyped as `Option<Box<{b}{v}>>`.
+///
+struct BorrowedVariantLifetime<'a> {
+    variant_ident: &'a Ident,
+    lifetime: &'a Lifetime,
+}
+
+impl<'a> BorrowedVariantLifetime<'a> {
+    fn new(variant_ident: &'a Ident, lifetime: &'a Lifetime) -> Self {
+        Self {
+            variant_ident,
+            lifetime,
+        }
+    }
+}
+
+impl VisitMut for BorrowedVariantLifetime<'_> {
+    fn visit_type_path_mut(&mut self, node: &mut TypePath) {
+        visit_type_path_mut(self, node);
+
+        let segment = match node.path.segments.last_mut() {
+            Some(segment) if segment.ident == *self.variant_ident => segment,
+            _ => return,
+        };
+
+        let lifetime = self.lifetime;
+
+        match &mut segment.arguments {
+            PathArguments::AngleBracketed(arguments) => {
+                arguments.args.insert(0, parse_quote!(#lifetime));
+            }
+            arguments => *arguments = PathArguments::AngleBracketed(parse_quote!(<#lifetime>)),
+        }
     }
 }
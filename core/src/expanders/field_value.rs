@@ -32,13 +32,11 @@ impl VisitMut for FieldValueExpander<'_> {
         let settings = match self.field_value_ctx.settings.get(variant) {
             Some(context) => context,
             None => {
-                self.print_field = false;
+                self.print_field = self.context.ctx.includes_by_default(variant);
                 return;
             }
         };
 
-        if &settings.variant != variant {
-            self.print_field = false;
-        }
+        self.print_field = settings.include;
     }
 }
@@ -1,16 +1,18 @@
 use proc_macro2::Span;
-use syn::{Error, ItemImpl, Type, visit_mut::VisitMut};
+use syn::{Error, ItemImpl, PathArguments, Type, parse_quote, visit_mut::VisitMut};
 
 use crate::{context::r#impl::ImplContext, utilities::type_ext::TypePathExt};
 
 use super::{
     Context,
     block::BlockExpander,
+    collapse_empty::CollapseEmptyExpander,
     expr_structs::ExprStructExpander,
     macros::{
         base::BaseMacro, replace_base::ReplaceBaseMacro, ty::TyMacro, type_str::TypeStrMacro,
         variant_str::VariantStrMacro,
     },
+    rename_case::RenameCaseExpander,
 };
 
 pub struct ImplExpander<'a> {
@@ -51,7 +53,8 @@ impl VisitMut for ImplExpander<'_> {
             }
         };
 
-        let mut type_str_macro = TypeStrMacro::new(&ty_path, &self.context.variant);
+        let mut type_str_macro =
+            TypeStrMacro::new(ty_path, &self.context.ctx.naming, &self.context.variant);
         type_str_macro.visit_item_impl_mut(node);
         self.errors.append(&mut type_str_macro.errors);
 
@@ -59,7 +62,8 @@ impl VisitMut for ImplExpander<'_> {
         variant_str_macro.visit_item_impl_mut(node);
         self.errors.append(&mut variant_str_macro.errors);
 
-        let mut type_macro = TyMacro::new(&base_ty, &self.context.variant);
+        let mut type_macro =
+            TyMacro::new(&base_ty, &self.context.ctx.naming, &self.context.variant);
         type_macro.visit_item_impl_mut(node);
         self.errors.append(&mut type_macro.errors);
 
@@ -72,12 +76,41 @@ impl VisitMut for ImplExpander<'_> {
         expr_struct_expander.visit_item_impl_mut(node);
         self.errors.append(&mut expr_struct_expander.errors);
 
+        let base_ident = match ty_path.path.segments.last() {
+            Some(segment) => &segment.ident,
+            None => return,
+        };
+
+        if self.context.ctx.collapse_empty && self.context.variant.is_some() {
+            let variant_ident = self.context.variant_ident(base_ident);
+            CollapseEmptyExpander::new(&variant_ident).visit_item_impl_mut(node);
+        }
+
+        let mut rename_case_expander = RenameCaseExpander::new(self.context, base_ident);
+        rename_case_expander.visit_item_impl_mut(node);
+
         if let Some(base_ty) = &base_macro.base_type {
-            let mut replace_base_macro = ReplaceBaseMacro::new(base_ty, &self.context.variant);
+            let mut replace_base_macro =
+                ReplaceBaseMacro::new(base_ty, &self.context.ctx.naming, &self.context.variant);
             replace_base_macro.visit_type_mut(&mut node.self_ty);
             self.errors.append(&mut replace_base_macro.errors);
         } else if let Some(variant) = self.context.variant {
-            node.self_ty = Box::new(ty_path.from_appendix(variant).into_type());
+            *node.self_ty = ty_path
+                .to_variant(&self.context.ctx.naming, variant)
+                .into_type();
+        }
+
+        // Borrowed variants take a lifetime, which is left to be inferred.
+        if self.context.borrow_lifetime().is_some()
+            && let Type::Path(ty_path) = node.self_ty.as_mut()
+            && let Some(segment) = ty_path.path.segments.last_mut()
+        {
+            match &mut segment.arguments {
+                PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.insert(0, parse_quote!('_));
+                }
+                arguments => *arguments = PathArguments::AngleBracketed(parse_quote!(<'_>)),
+            }
         }
     }
 }
@@ -1,11 +1,12 @@
 use syn::{Error, Ident, Type, visit_mut::VisitMut};
 
-use crate::utilities::type_ext::TypePathExt;
+use crate::{context::naming::Naming, utilities::type_ext::TypePathExt};
 
 use super::insert_type::InsertTypeMacro;
 
 pub struct ReplaceBaseMacro<'a> {
     base_type: &'a Type,
+    naming: &'a Naming,
     variant: &'a Option<&'a Ident>,
     pub errors: Vec<Error>,
 }
@@ -13,11 +14,13 @@ pub struct ReplaceBaseMacro<'a> {
 impl<'a> ReplaceBaseMacro<'a> {
     const IDENTIFIER: &'static str = "base";
 
-    /// Replaces the `base!(type)` macro with the given type.
+    /// Replaces the `base!(type)` macro with the variant type of the given type, named following
+    /// `naming`. If the variant is `None`, just the given type is inserted.
     ///
-    pub fn new(base_type: &'a Type, variant: &'a Option<&'a Ident>) -> Self {
+    pub fn new(base_type: &'a Type, naming: &'a Naming, variant: &'a Option<&'a Ident>) -> Self {
         Self {
             base_type,
+            naming,
             variant,
             errors: Vec::new(),
         }
@@ -30,7 +33,7 @@ impl VisitMut for ReplaceBaseMacro<'_> {
             .variant
             .and_then(|variant| {
                 if let Type::Path(type_path) = &self.base_type {
-                    Some(Type::Path(type_path.clone().from_appendix(variant)))
+                    Some(Type::Path(type_path.to_variant(self.naming, variant)))
                 } else {
                     None
                 }
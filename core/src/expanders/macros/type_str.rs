@@ -1,5 +1,7 @@
 use syn::{Error, Expr, Ident, Macro, TypePath, visit_mut::VisitMut};
 
+use crate::{context::naming::Naming, utilities::ident_ext::IdentExt};
+
 use super::insert_str::InsertStrMacro;
 
 pub struct TypeStrMacro {
@@ -13,24 +15,18 @@ impl TypeStrMacro {
     /// Replace all `type_str!()` expression macros with a string literal that is a combination of
     /// `base_path` and `variant`.
     ///
-    /// The `variant` string value is appended to the last segment of `base_path` to form the
+    /// The last segment of `base_path` and `variant` are combined following `naming` to form the
     /// replacement string. If `variant` is `None`, just the last segment of `base_path` is used.
     ///
-    pub fn new(base_path: &TypePath, variant: &Option<&Ident>) -> Self {
-        let base_str = base_path
-            .path
-            .segments
-            .last()
-            .map(|segment| segment.ident.to_string())
-            .unwrap_or("".into());
-
-        let variant_str = match variant {
-            Some(variant) => variant.to_string(),
-            None => "".into(),
+    pub fn new(base_path: &TypePath, naming: &Naming, variant: &Option<&Ident>) -> Self {
+        let type_str = match (base_path.path.segments.last(), variant) {
+            (Some(segment), Some(variant)) => segment.ident.to_variant(naming, variant).to_string(),
+            (Some(segment), None) => segment.ident.to_string(),
+            (None, _) => "".into(),
         };
 
         Self {
-            type_str: format!("{}{}", base_str, variant_str),
+            type_str,
             errors: Vec::new(),
         }
     }
@@ -0,0 +1,52 @@
+use syn::{
+    Ident, Path,
+    visit_mut::{VisitMut, visit_path_mut},
+};
+
+use super::Context;
+
+pub struct RenameCaseExpander<'a> {
+    context: &'a Context<'a>,
+    base_ident: &'a Ident,
+}
+
+impl<'a> RenameCaseExpander<'a> {
+    /// Renames the enum cases in paths such as `Self::Old { .. }` or `Self::Old(..)`, in both
+    /// expressions and patterns, following the `rename(..)` option of the current variant. Paths
+    /// through the enum of any variant of `base_ident`, such as `FooV2::Old`, follow the option
+    /// of that variant instead.
+    ///
+    pub fn new(context: &'a Context, base_ident: &'a Ident) -> Self {
+        Self {
+            context,
+            base_ident,
+        }
+    }
+}
+
+impl VisitMut for RenameCaseExpander<'_> {
+    fn visit_path_mut(&mut self, node: &mut Path) {
+        let len = node.segments.len();
+
+        if len >= 2 {
+            let enum_ident = &node.segments[len - 2].ident;
+
+            let variant = match enum_ident == "Self" {
+                true => self.context.variant,
+                false => self.context.ctx.variants.iter().find(|variant| {
+                    Context::new(self.context.ctx, Some(variant)).variant_ident(self.base_ident)
+                        == *enum_ident
+                }),
+            };
+
+            if let Some(variant) = variant
+                && let Ok(Some(ident)) = Context::new(self.context.ctx, Some(variant))
+                    .case_rename(&node.segments[len - 1].ident, None)
+            {
+                node.segments[len - 1].ident = ident;
+            }
+        }
+
+        visit_path_mut(self, node);
+    }
+}
@@ -37,7 +37,7 @@ impl VisitMut for StmtExpander<'_> {
         self.print_stmt = settings.include;
 
         if settings.vary_type {
-            let mut vary_type_expander = VaryTypeExpander::new(&variant);
+            let mut vary_type_expander = VaryTypeExpander::new(&self.context.ctx.naming, variant);
             vary_type_expander.visit_stmt_mut(node);
             self.errors.append(&mut vary_type_expander.errors);
         }
@@ -1,9 +1,6 @@
-use syn::{Error, ItemStruct, visit_mut::VisitMut};
+use syn::{Error, Fields, ItemStruct, visit_mut::VisitMut};
 
-use crate::{
-    context::r#struct::StructContext,
-    utilities::{fields_ext::FieldsExt, ident_ext::IdentExt},
-};
+use crate::{context::r#struct::StructContext, utilities::fields_ext::FieldsExt};
 
 use super::{Context, field::FieldExpander};
 
@@ -48,8 +45,13 @@ impl VisitMut for StructExpander<'_> {
 
         node.fields.replace_fields(print_fields);
 
-        if let Some(variant) = self.context.variant {
-            node.ident = node.ident.from_appendix(variant);
+        if self.context.collapses(&node.fields) {
+            node.fields = Fields::Unit;
+            node.semi_token = Some(Default::default());
         }
+
+        node.generics = self.context.variant_generics(&node.generics);
+        self.context.replace_repr(&mut node.attrs);
+        node.ident = self.context.variant_ident(&node.ident);
     }
 }
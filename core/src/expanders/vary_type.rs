@@ -1,17 +1,20 @@
 use syn::{Error, ExprStruct, Ident, Path, visit_mut::VisitMut};
 
-use crate::utilities::path_ext::PathExt;
+use crate::{context::naming::Naming, utilities::path_ext::PathExt};
 
 pub struct VaryTypeExpander<'a> {
+    naming: &'a Naming,
     variant: &'a Ident,
     pub errors: Vec<Error>,
 }
 
 impl<'a> VaryTypeExpander<'a> {
-    /// Appends `variant` to the type in the first next expression.
+    /// Replaces the type in the first next expression with its `variant` counterpart, named
+    /// following `naming`.
     ///
-    pub fn new(variant: &'a Ident) -> Self {
+    pub fn new(naming: &'a Naming, variant: &'a Ident) -> Self {
         Self {
+            naming,
             variant,
             errors: Vec::new(),
         }
@@ -24,6 +27,6 @@ impl VisitMut for VaryTypeExpander<'_> {
     }
 
     fn visit_path_mut(&mut self, node: &mut Path) {
-        *node = node.from_appendix(self.variant);
+        *node = node.to_variant(self.naming, self.variant);
     }
 }
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use syn::{
+    Error, Expr, Field, Fields, Ident, Index, Item, Member, Type, Variant, parse_quote,
+    spanned::Spanned, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{
+        r#enum::EnumContext,
+        field::{FieldContext, VariantSettings},
+    },
+    expanders::{Context, case::CaseExpander, field::FieldExpander},
+    utilities::attribute_remover::AttributeRemover,
+};
+
+pub mod accessor;
+pub mod builder;
+pub mod complete;
+pub mod conversions;
+pub mod default;
+pub mod diff;
+pub mod from_base;
+pub mod item;
+pub mod kind;
+pub mod migrate;
+pub mod patch;
+pub mod split_cases;
+pub mod validate;
+
+/// A generator of the items that come along with the variants, run by `ItemGenerator`.
+///
+pub trait Generator: VisitMut {
+    /// The items generated, along with the errors encountered.
+    ///
+    fn into_output(self) -> (Vec<Item>, Vec<Error>);
+}
+
+/// A field of a base struct or enum case that is part of a variant, or that is missing from it.
+///
+pub struct VariantField<'a> {
+    /// The member of the field in the base item.
+    pub base_member: Member,
+
+    /// The member of the field in the variant item, which differs from `base_member` for tuple
+    /// fields that follow fields of the other kind.
+    pub member: Member,
+
+    /// The type of the field in the base item.
+    pub base_ty: Type,
+
+    /// The field as expanded for the variant, with its retype applied, or the base field for
+    /// missing fields.
+    pub field: Field,
+
+    /// The settings of the field for the variant, if it has any.
+    pub settings: Option<&'a VariantSettings>,
+}
+
+impl VariantField<'_> {
+    /// The expression that converts the value `value` of the base field into a value of the
+    /// variant field: the field-level `convert` function if set, `Into::into` if the field is
+    /// retyped, or the value as is otherwise. The value is first transformed by the field-level
+    /// `project` function, if set.
+    ///
+    pub fn convert(&self, value: Expr) -> Expr {
+        let value = match self.settings.and_then(|settings| settings.project.as_ref()) {
+            Some(project) => parse_quote!(#project(#value)),
+            None => value,
+        };
+
+        match self.settings {
+            Some(settings) if settings.convert.is_some() => {
+                let convert = &settings.convert;
+                parse_quote!(#convert(#value))
+            }
+            Some(settings) if settings.retype.is_some() => {
+                parse_quote!(::core::convert::Into::into(#value))
+            }
+            _ => value,
+        }
+    }
+
+    /// The name the base field is bound to when destructuring, as in `Foo::Case { 0: field_0 }`.
+    ///
+    pub fn binding(&self) -> Ident {
+        binding(&self.base_member)
+    }
+}
+
+/// Expand each of the `fields` of the struct or enum `base_ident` in `context`, and collect the
+/// ones that are part of it.
+///
+pub fn variant_fields<'a>(
+    context: &Context,
+    base_ident: &Ident,
+    field_ctxs: &'a HashMap<Field, FieldContext<'a>>,
+    fields: &Fields,
+) -> Vec<VariantField<'a>> {
+    partition_fields(context, base_ident, field_ctxs, fields).0
+}
+
+/// Collect the `fields` of the struct or enum `base_ident` that are not part of it in `context`.
+/// The members and fields of the returned fields are those of a struct with only these fields.
+///
+pub fn missing_fields<'a>(
+    context: &Context,
+    base_ident: &Ident,
+    field_ctxs: &'a HashMap<Field, FieldContext<'a>>,
+    fields: &Fields,
+) -> Vec<VariantField<'a>> {
+    partition_fields(context, base_ident, field_ctxs, fields).1
+}
+
+fn partition_fields<'a>(
+    context: &Context,
+    base_ident: &Ident,
+    field_ctxs: &'a HashMap<Field, FieldContext<'a>>,
+    fields: &Fields,
+) -> (Vec<VariantField<'a>>, Vec<VariantField<'a>>) {
+    let mut variant_fields = Vec::new();
+    let mut missing_fields = Vec::new();
+
+    for (base_member, field) in fields.members().zip(fields.iter()) {
+        let field_ctx = match field_ctxs.get(field) {
+            Some(field_ctx) => field_ctx,
+            None => continue,
+        };
+
+        let mut variant_field = field.clone();
+        let mut field_expander = FieldExpander::new(context, base_ident, field_ctx);
+        field_expander.visit_field_mut(&mut variant_field);
+
+        let partition = match field_expander.print_field {
+            true => &mut variant_fields,
+            false => {
+                variant_field = field.clone();
+                &mut missing_fields
+            }
+        };
+
+        AttributeRemover::new().visit_field_mut(&mut variant_field);
+
+        let member = match &base_member {
+            Member::Named(ident) => Member::Named(ident.clone()),
+            Member::Unnamed(_) => Member::Unnamed(Index {
+                index: partition.len() as u32,
+                span: field.span(),
+            }),
+        };
+
+        partition.push(VariantField {
+            base_member,
+            member,
+            base_ty: field.ty.clone(),
+            field: variant_field,
+            settings: context
+                .variant
+                .and_then(|variant| field_ctx.settings.get(variant)),
+        });
+    }
+
+    (variant_fields, missing_fields)
+}
+
+/// Expand a single enum case of the enum `enum_base_ident` in `context`, or `None` if the case is
+/// not part of it. Errors are ignored, as they are already reported by the expansion of the enum
+/// itself.
+///
+pub fn expand_case(
+    context: &Context,
+    enum_base_ident: &Ident,
+    enum_ctx: &EnumContext,
+    case: &Variant,
+) -> Option<Variant> {
+    let case_ctx = enum_ctx.case_ctxs.get(case)?;
+
+    let mut case = case.clone();
+    let mut case_expander = CaseExpander::new(context, enum_base_ident, enum_ctx, case_ctx);
+    case_expander.visit_variant_mut(&mut case);
+
+    case_expander.print_case.then_some(case)
+}
+
+/// The name a field is bound to when destructuring, as in `Foo::Case { 0: field_0 }`.
+///
+pub fn binding(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => Ident::new(&format!("field_{}", index.index), index.span),
+    }
+}
@@ -0,0 +1,175 @@
+use syn::{
+    Arm, Error, Expr, Generics, Ident, Item, ItemEnum, ItemStruct, Visibility, parse_quote,
+    visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{self, item::ItemContext},
+    expanders::Context,
+};
+
+use super::{Generator, VariantField, expand_case, variant_fields};
+
+pub struct AccessorGenerator<'a> {
+    ctx: &'a context::Context,
+    item: &'a ItemContext<'a>,
+    variant: &'a Ident,
+    accessor: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> AccessorGenerator<'a> {
+    /// Generates a method named `accessor` on the base, which creates the borrowed variant
+    /// `variant` from a reference to the base. Fields with a `convert` function are converted
+    /// from a reference to the base field, whereas other fields are coerced into their type.
+    ///
+    pub fn new(
+        ctx: &'a context::Context,
+        item: &'a ItemContext<'a>,
+        variant: &'a Ident,
+        accessor: &'a Ident,
+    ) -> Self {
+        Self {
+            ctx,
+            item,
+            variant,
+            accessor,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Push the accessor method, returning `body`, on the base `base_ident` with the generics
+    /// `base`.
+    ///
+    fn push_accessor(&mut self, base_ident: &Ident, base: &Generics, vis: &Visibility, body: Expr) {
+        let context = Context::new(self.ctx, Some(self.variant));
+        let variant_ident = context.variant_ident(base_ident);
+        let variant_generics = context.variant_generics(base);
+        let lifetime = context.borrow_lifetime();
+        let accessor = self.accessor;
+
+        let (impl_generics, variant_ty_generics, where_clause) = variant_generics.split_for_impl();
+        let (_, ty_generics, _) = base.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #base_ident #ty_generics #where_clause {
+                #vis fn #accessor(&#lifetime self) -> #variant_ident #variant_ty_generics {
+                    #body
+                }
+            }
+        });
+    }
+}
+
+impl Generator for AccessorGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for AccessorGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let struct_ctx = self
+            .item
+            .struct_ctx
+            .as_ref()
+            .expect("struct context should exist");
+
+        let context = Context::new(self.ctx, Some(self.variant));
+
+        let fields = variant_fields(&context, &node.ident, &struct_ctx.field_ctxs, &node.fields);
+        let members = fields.iter().map(|field| &field.member);
+        let values = fields.iter().map(|field| {
+            let base_member = &field.base_member;
+            borrow(field, parse_quote!(&self.#base_member))
+        });
+
+        let variant_ident = context.variant_ident(&node.ident);
+        let vis = context.variant_vis(&node.vis);
+
+        self.push_accessor(
+            &node.ident,
+            &node.generics,
+            &vis,
+            parse_quote!(#variant_ident { #(#members: #values,)* }),
+        );
+    }
+
+    fn visit_item_enum_mut(&mut self, node: &mut ItemEnum) {
+        let enum_ctx = self
+            .item
+            .enum_ctx
+            .as_ref()
+            .expect("enum context should exist");
+
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+        let variant_ident = context.variant_ident(base_ident);
+
+        let mut arms = Vec::<Arm>::new();
+
+        for case in &node.variants {
+            let variant_case = match expand_case(&context, base_ident, enum_ctx, case) {
+                Some(variant_case) => variant_case,
+                None => {
+                    self.errors.push(Error::new(
+                        case.ident.span(),
+                        format!(
+                            "cannot borrow `{}` as `{}`, which lacks the case `{}`",
+                            base_ident, variant_ident, case.ident
+                        ),
+                    ));
+                    continue;
+                }
+            };
+
+            let case_ident = &case.ident;
+            let variant_case_ident = &variant_case.ident;
+
+            let fields = variant_fields(&context, base_ident, &enum_ctx.field_ctxs, &case.fields);
+            let base_members = fields.iter().map(|field| &field.base_member);
+            let bindings = fields
+                .iter()
+                .map(|field| field.binding())
+                .collect::<Vec<_>>();
+            let members = fields.iter().map(|field| &field.member);
+            let values = fields
+                .iter()
+                .zip(&bindings)
+                .map(|(field, binding)| borrow(field, parse_quote!(#binding)));
+
+            arms.push(parse_quote! {
+                #base_ident::#case_ident { #(#base_members: #bindings,)* .. } => {
+                    #variant_ident::#variant_case_ident { #(#members: #values,)* }
+                }
+            });
+        }
+
+        let vis = context.variant_vis(&node.vis);
+
+        self.push_accessor(
+            base_ident,
+            &node.generics,
+            &vis,
+            parse_quote! {
+                match self {
+                    #(#arms,)*
+                }
+            },
+        );
+    }
+}
+
+/// The expression that borrows the base field referenced by `value` for the variant field.
+///
+fn borrow(field: &VariantField, value: Expr) -> Expr {
+    match field
+        .settings
+        .and_then(|settings| settings.convert.as_ref())
+    {
+        Some(convert) => parse_quote!(#convert(#value)),
+        None => value,
+    }
+}
@@ -0,0 +1,148 @@
+use proc_macro2::Span;
+use syn::{Error, Expr, Ident, Item, ItemStruct, LitStr, parse_quote, visit_mut::VisitMut};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+    utilities::ident_ext::IdentExt,
+};
+
+use super::{Generator, binding, variant_fields};
+
+pub struct BuilderGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> BuilderGenerator<'a> {
+    /// Generates a builder for the variant `variant`, such as `FooSummaryBuilder` for
+    /// `FooSummary`, with a setter taking the type of each field in the variant, named after the
+    /// field, or as in `field_0` for tuple fields, which cannot be named `build` or `builder`. Its
+    /// `build()` method returns the name of the first missing field as an error, unless that field
+    /// has a `default` value for the variant.
+    ///
+    pub fn new(
+        ctx: &'a context::Context,
+        struct_ctx: &'a StructContext<'a>,
+        variant: &'a Ident,
+    ) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for BuilderGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for BuilderGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let context = Context::new(self.ctx, Some(self.variant));
+        let variant_ident = context.variant_ident(&node.ident);
+        let builder_ident = variant_ident.from_appendix(&Ident::new("Builder", Span::call_site()));
+
+        let fields = variant_fields(
+            &context,
+            &node.ident,
+            &self.struct_ctx.field_ctxs,
+            &node.fields,
+        );
+
+        let members = fields.iter().map(|field| &field.member).collect::<Vec<_>>();
+        let names = fields
+            .iter()
+            .map(|field| binding(&field.member))
+            .collect::<Vec<_>>();
+        let tys = fields
+            .iter()
+            .map(|field| &field.field.ty)
+            .collect::<Vec<_>>();
+
+        // The setters are named after the fields, and so must not take the names of the methods
+        // generated alongside them.
+        let collisions = names
+            .iter()
+            .filter(|name| *name == "build" || *name == "builder")
+            .collect::<Vec<_>>();
+
+        for name in &collisions {
+            self.errors.push(Error::new(
+                name.span(),
+                format!(
+                    "cannot generate the setter of the field `{}` of `{}`, which would collide \
+                     with the `{}` method",
+                    name, variant_ident, name
+                ),
+            ));
+        }
+
+        if !collisions.is_empty() {
+            return;
+        }
+
+        let values = fields.iter().zip(&names).map(|(field, name)| -> Expr {
+            match field
+                .settings
+                .and_then(|settings| settings.default.as_ref())
+            {
+                Some(default) => parse_quote!(self.#name.unwrap_or_else(|| #default)),
+                None => {
+                    let missing = LitStr::new(&name.to_string(), name.span());
+                    parse_quote!(self.#name.ok_or(#missing)?)
+                }
+            }
+        });
+
+        let vis = context.variant_vis(&node.vis);
+
+        let variant_generics = context.variant_generics(&node.generics);
+        let (impl_generics, ty_generics, where_clause) = variant_generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            #vis struct #builder_ident #variant_generics #where_clause {
+                #(#names: ::core::option::Option<#tys>,)*
+            }
+        });
+
+        self.items.push(parse_quote! {
+            impl #impl_generics ::core::default::Default for #builder_ident #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self { #(#names: ::core::option::Option::None,)* }
+                }
+            }
+        });
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #builder_ident #ty_generics #where_clause {
+                #(
+                    #vis fn #names(mut self, value: #tys) -> Self {
+                        self.#names = ::core::option::Option::Some(value);
+                        self
+                    }
+                )*
+
+                #vis fn build(self) -> ::core::result::Result<#variant_ident #ty_generics, &'static str> {
+                    ::core::result::Result::Ok(#variant_ident { #(#members: #values,)* })
+                }
+            }
+        });
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #variant_ident #ty_generics #where_clause {
+                #vis fn builder() -> #builder_ident #ty_generics {
+                    ::core::default::Default::default()
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,209 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{
+    Error, Expr, Fields, FieldsNamed, FieldsUnnamed, Ident, Item, ItemStruct, Token, parse_quote,
+    parse_quote_spanned, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+    utilities::{generics_ext::GenericsExt, ident_ext::IdentExt, vec_ext::VecExt},
+};
+
+use super::{Generator, missing_fields, variant_fields};
+
+pub struct CompleteGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> CompleteGenerator<'a> {
+    /// Generates a struct with the fields of the base that `variant` lacks, such as `FooCreateRest`
+    /// for `FooCreate`, and `fn complete(self, rest: FooCreateRest) -> Foo` on the variant.
+    /// Retyped fields are converted back into the base with `Into`, except for those wrapping the
+    /// base type, as `Option<{}>`, or holding a variant, as `Option<Box<{b}{v}>>`, which cannot be.
+    /// When every missing field has a `complete` value, `From<FooCreate> for Foo` is generated as
+    /// well.
+    ///
+    pub fn new(
+        ctx: &'a context::Context,
+        struct_ctx: &'a StructContext<'a>,
+        variant: &'a Ident,
+    ) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for CompleteGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for CompleteGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+        let variant_ident = context.variant_ident(base_ident);
+        let rest_ident = variant_ident.from_appendix(&Ident::new("Rest", Span::call_site()));
+
+        let fields = variant_fields(
+            &context,
+            base_ident,
+            &self.struct_ctx.field_ctxs,
+            &node.fields,
+        );
+        let missing = missing_fields(
+            &context,
+            base_ident,
+            &self.struct_ctx.field_ctxs,
+            &node.fields,
+        );
+
+        /* The struct with the missing fields.
+         */
+
+        let rest_fields = missing
+            .iter()
+            .map(|field| field.field.clone())
+            .collect::<Vec<_>>();
+
+        let (rest_fields, semi_token) = match &node.fields {
+            Fields::Named(_) => (
+                Fields::Named(FieldsNamed {
+                    brace_token: Default::default(),
+                    named: rest_fields.into_punctuated(),
+                }),
+                None,
+            ),
+            _ => (
+                Fields::Unnamed(FieldsUnnamed {
+                    paren_token: Default::default(),
+                    unnamed: rest_fields.into_punctuated(),
+                }),
+                Some(<Token![;]>::default()),
+            ),
+        };
+
+        let mut rest_generics = node.generics.clone();
+        rest_generics.retain_used(&rest_fields.to_token_stream());
+
+        let vis = context.variant_vis(&node.vis);
+
+        let mut attrs = node.attrs.clone();
+
+        if let Some(variant_ctx) = context.variant_ctx() {
+            attrs.extend(variant_ctx.attrs.iter().cloned());
+        }
+
+        context.replace_repr(&mut attrs);
+
+        self.items.push(Item::Struct(ItemStruct {
+            attrs,
+            vis: vis.clone(),
+            struct_token: node.struct_token,
+            ident: rest_ident.clone(),
+            generics: rest_generics.clone(),
+            fields: rest_fields,
+            semi_token,
+        }));
+
+        /* The method completing the variant.
+         */
+
+        let mut base_members = Vec::new();
+        let mut values = Vec::<Expr>::new();
+
+        for field in &fields {
+            let member = &field.member;
+            let base_member = &field.base_member;
+
+            let value = match field.settings.and_then(|settings| settings.retype.as_ref()) {
+                Some(retype) if is_irreversible(&retype.value()) => {
+                    self.errors.push(Error::new(
+                        retype.span(),
+                        format!(
+                            "cannot complete `{}` into `{}`, as the retype of the field `{}` \
+                             cannot be converted back",
+                            variant_ident,
+                            base_ident,
+                            base_member.to_token_stream()
+                        ),
+                    ));
+                    continue;
+                }
+                Some(retype) => {
+                    let base_ty = &field.base_ty;
+                    parse_quote_spanned! {retype.span()=>
+                        ::core::convert::Into::<#base_ty>::into(self.#member)
+                    }
+                }
+                None => parse_quote!(self.#member),
+            };
+
+            base_members.push(base_member);
+            values.push(value);
+        }
+
+        for field in &missing {
+            let member = &field.member;
+
+            base_members.push(&field.base_member);
+            values.push(parse_quote!(rest.#member));
+        }
+
+        let variant_generics = context.variant_generics(&node.generics);
+        let (impl_generics, variant_ty_generics, where_clause) = variant_generics.split_for_impl();
+        let (_, ty_generics, _) = node.generics.split_for_impl();
+        let (_, rest_ty_generics, _) = rest_generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #variant_ident #variant_ty_generics #where_clause {
+                #vis fn complete(self, rest: #rest_ident #rest_ty_generics) -> #base_ident #ty_generics {
+                    #base_ident { #(#base_members: #values,)* }
+                }
+            }
+        });
+
+        /* The conversion completing the variant with default values.
+         */
+
+        let defaults = missing
+            .iter()
+            .map(|field| field.settings?.complete.as_ref())
+            .collect::<Option<Vec<_>>>();
+
+        if let Some(defaults) = defaults {
+            let members = missing.iter().map(|field| &field.member);
+
+            self.items.push(parse_quote! {
+                impl #impl_generics From<#variant_ident #variant_ty_generics> for #base_ident #ty_generics #where_clause {
+                    fn from(value: #variant_ident #variant_ty_generics) -> Self {
+                        value.complete(#rest_ident { #(#members: #defaults,)* })
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Whether `retype` cannot be converted back into the type of the base field with `Into`, as it
+/// wraps that type, as `Option<{}>` or `Vec<{t}>`, or holds a variant, as `Box<{b}{v}>`.
+///
+fn is_irreversible(retype: &str) -> bool {
+    let retype = retype.trim();
+    let wraps =
+        (retype.contains("{}") || retype.contains("{t}")) && retype != "{}" && retype != "{t}";
+
+    wraps || retype.contains("{b}{v}")
+}
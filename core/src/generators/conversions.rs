@@ -0,0 +1,176 @@
+use quote::ToTokens;
+use syn::{Error, Ident, Item, ItemStruct, parse_quote, visit_mut::VisitMut};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+};
+
+use super::{Generator, variant_fields};
+
+pub struct ConversionsGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> ConversionsGenerator<'a> {
+    /// Generates `From<FooDetail> for FooSummary` for pairs of variants where the fields of
+    /// `FooSummary` are a subset of those of `FooDetail`, with the same types. Either all such
+    /// pairs of variants are converted, or only the requested ones, which must then be valid.
+    /// Borrowed variants are not converted, nor versions into the next, which are already
+    /// converted by their migrations.
+    ///
+    pub fn new(ctx: &'a context::Context, struct_ctx: &'a StructContext<'a>) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl ConversionsGenerator<'_> {
+    /// Whether `into` is the version following `from`, as migrated by `MigrateGenerator`.
+    ///
+    fn is_migration(&self, from: &Ident, into: &Ident) -> bool {
+        self.ctx
+            .variants
+            .windows(2)
+            .any(|pair| pair[0] == *from && pair[1] == *into)
+    }
+}
+
+impl Generator for ConversionsGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for ConversionsGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let requested = !self.ctx.conversions.is_empty();
+
+        let pairs = match requested {
+            true => self
+                .ctx
+                .conversions
+                .iter()
+                .map(|(from, into)| (from, into))
+                .collect::<Vec<_>>(),
+            false => self
+                .ctx
+                .variants
+                .iter()
+                .flat_map(|from| self.ctx.variants.iter().map(move |into| (from, into)))
+                .filter(|(from, into)| from != into)
+                .collect(),
+        };
+
+        let (impl_generics, ty_generics, where_clause) = node.generics.split_for_impl();
+
+        for (from, into) in pairs {
+            let from_context = Context::new(self.ctx, Some(from));
+            let into_context = Context::new(self.ctx, Some(into));
+
+            let from_ident = from_context.variant_ident(&node.ident);
+            let into_ident = into_context.variant_ident(&node.ident);
+
+            let conversion = format!("`{}` into `{}`", from_ident, into_ident);
+
+            if self.ctx.versioned && self.is_migration(from, into) {
+                if requested {
+                    self.errors.push(Error::new(
+                        from.span(),
+                        format!(
+                            "cannot convert {}, as it is already converted by its migration",
+                            conversion
+                        ),
+                    ));
+                }
+                continue;
+            }
+
+            if [&from_context, &into_context]
+                .iter()
+                .any(|context| context.borrow_lifetime().is_some())
+            {
+                if requested {
+                    self.errors.push(Error::new(
+                        from.span(),
+                        format!(
+                            "cannot convert {}, as borrowed variants cannot be converted",
+                            conversion
+                        ),
+                    ));
+                }
+                continue;
+            }
+
+            let from_fields = variant_fields(
+                &from_context,
+                &node.ident,
+                &self.struct_ctx.field_ctxs,
+                &node.fields,
+            );
+            let into_fields = variant_fields(
+                &into_context,
+                &node.ident,
+                &self.struct_ctx.field_ctxs,
+                &node.fields,
+            );
+
+            let mut members = Vec::new();
+            let mut from_members = Vec::new();
+            let mut mismatch = None;
+
+            for into_field in &into_fields {
+                let from_field = from_fields
+                    .iter()
+                    .find(|from_field| from_field.base_member == into_field.base_member);
+
+                match from_field {
+                    Some(from_field) if from_field.field.ty == into_field.field.ty => {
+                        members.push(&into_field.member);
+                        from_members.push(&from_field.member);
+                    }
+                    Some(_) => {
+                        mismatch = Some(format!(
+                            "the field `{}` has different types",
+                            into_field.base_member.to_token_stream()
+                        ));
+                        break;
+                    }
+                    None => {
+                        mismatch = Some(format!(
+                            "`{}` lacks the field `{}`",
+                            from_ident,
+                            into_field.base_member.to_token_stream()
+                        ));
+                        break;
+                    }
+                }
+            }
+
+            if let Some(mismatch) = mismatch {
+                if requested {
+                    self.errors.push(Error::new(
+                        from.span(),
+                        format!("cannot convert {}, as {}", conversion, mismatch),
+                    ));
+                }
+                continue;
+            }
+
+            self.items.push(parse_quote! {
+                impl #impl_generics From<#from_ident #ty_generics> for #into_ident #ty_generics #where_clause {
+                    fn from(value: #from_ident #ty_generics) -> Self {
+                        Self { #(#members: value.#from_members,)* }
+                    }
+                }
+            });
+        }
+    }
+}
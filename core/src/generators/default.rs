@@ -0,0 +1,92 @@
+use syn::{Error, Expr, Ident, Item, ItemStruct, parse_quote, visit_mut::VisitMut};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+};
+
+use super::{Generator, variant_fields};
+
+pub struct DefaultGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> DefaultGenerator<'a> {
+    /// Generates `Default` for the variant `variant`, with the `default` value of each field for
+    /// the variant, or `Default::default()` for fields without one. As with `#[derive(Default)]`,
+    /// every type parameter is required to implement `Default`.
+    ///
+    pub fn new(
+        ctx: &'a context::Context,
+        struct_ctx: &'a StructContext<'a>,
+        variant: &'a Ident,
+    ) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for DefaultGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for DefaultGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let context = Context::new(self.ctx, Some(self.variant));
+        let variant_ident = context.variant_ident(&node.ident);
+
+        let fields = variant_fields(
+            &context,
+            &node.ident,
+            &self.struct_ctx.field_ctxs,
+            &node.fields,
+        );
+        let members = fields.iter().map(|field| &field.member);
+        let values = fields.iter().map(|field| -> Expr {
+            match field
+                .settings
+                .and_then(|settings| settings.default.as_ref())
+            {
+                Some(default) => default.clone(),
+                None => parse_quote!(::core::default::Default::default()),
+            }
+        });
+
+        let mut variant_generics = context.variant_generics(&node.generics);
+        let bounded = variant_generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect::<Vec<_>>();
+
+        if !bounded.is_empty() {
+            let where_clause = variant_generics.make_where_clause();
+
+            for ident in bounded {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#ident: ::core::default::Default));
+            }
+        }
+
+        let (impl_generics, ty_generics, where_clause) = variant_generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics ::core::default::Default for #variant_ident #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self { #(#members: #values,)* }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,146 @@
+use quote::ToTokens;
+use syn::{
+    Error, Expr, Ident, Item, ItemStruct, parse_quote, spanned::Spanned, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+};
+
+use super::{
+    Generator,
+    patch::{PatchField, Wrapper},
+    variant_fields,
+};
+
+pub struct DiffGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> DiffGenerator<'a> {
+    /// Generates `fn diff(old: &Foo, new: &Foo) -> Self` on the patch variant `variant`, which
+    /// creates the patch that turns `old` into `new` when applied. Fields are compared with
+    /// `PartialEq` and cloned from `new`. Nested patches can only describe changes between two
+    /// `Some` values, so nested fields through an `Option` of the base, as `Option<Box<Foo>>`, are
+    /// rejected rather than losing the changes between `None` and `Some`.
+    ///
+    pub fn new(
+        ctx: &'a context::Context,
+        struct_ctx: &'a StructContext<'a>,
+        variant: &'a Ident,
+    ) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for DiffGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for DiffGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+        let variant_ident = context.variant_ident(base_ident);
+
+        let fields = variant_fields(
+            &context,
+            base_ident,
+            &self.struct_ctx.field_ctxs,
+            &node.fields,
+        );
+        let mut members = Vec::new();
+        let mut values = Vec::<Expr>::new();
+
+        for field in &fields {
+            let base_member = &field.base_member;
+
+            let value = match PatchField::new(field, base_ident, &variant_ident, &mut self.errors) {
+                Some(PatchField::Always) => parse_quote!(new.#base_member.clone()),
+                Some(PatchField::Optional) => parse_quote! {
+                    (old.#base_member != new.#base_member).then(|| new.#base_member.clone())
+                },
+                Some(PatchField::Nested(wrappers, optional)) => {
+                    if wrappers
+                        .iter()
+                        .any(|wrapper| matches!(wrapper, Wrapper::Option))
+                    {
+                        self.errors.push(Error::new(
+                            field.field.ty.span(),
+                            format!(
+                                "cannot diff the field `{}`, as a nested patch cannot describe the \
+                                 changes between `None` and `Some`",
+                                base_member.to_token_stream()
+                            ),
+                        ));
+                        continue;
+                    }
+
+                    let diff = diff_nested(
+                        wrappers.len(),
+                        &variant_ident,
+                        parse_quote!(&old.#base_member),
+                        parse_quote!(&new.#base_member),
+                    );
+
+                    match optional {
+                        true => parse_quote! {
+                            (old.#base_member != new.#base_member).then(|| #diff)
+                        },
+                        false => diff,
+                    }
+                }
+                None => continue,
+            };
+
+            members.push(&field.member);
+            values.push(value);
+        }
+
+        let vis = context.variant_vis(&node.vis);
+
+        let variant_generics = context.variant_generics(&node.generics);
+        let (impl_generics, variant_ty_generics, where_clause) = variant_generics.split_for_impl();
+        let (_, ty_generics, _) = node.generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #variant_ident #variant_ty_generics #where_clause {
+                #vis fn diff(old: &#base_ident #ty_generics, new: &#base_ident #ty_generics) -> Self {
+                    Self { #(#members: #values,)* }
+                }
+            }
+        });
+    }
+}
+
+/// Create the expression creating the nested patch between the references `old` and `new`,
+/// through `boxes` wrapping `Box`es.
+///
+fn diff_nested(boxes: usize, variant_ident: &Ident, old: Expr, new: Expr) -> Expr {
+    match boxes {
+        0 => parse_quote!(#variant_ident::diff(#old, #new)),
+        _ => {
+            let diff = diff_nested(
+                boxes - 1,
+                variant_ident,
+                parse_quote!(&**#old),
+                parse_quote!(&**#new),
+            );
+
+            parse_quote!(Box::new(#diff))
+        }
+    }
+}
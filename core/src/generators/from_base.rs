@@ -0,0 +1,166 @@
+use syn::{
+    Arm, Error, Generics, Ident, Item, ItemEnum, ItemStruct, parse_quote, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{self, item::ItemContext},
+    expanders::Context,
+};
+
+use super::{Generator, expand_case, variant_fields};
+
+pub struct FromBaseGenerator<'a> {
+    ctx: &'a context::Context,
+    item: &'a ItemContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> FromBaseGenerator<'a> {
+    /// Generates `From<Foo> for FooVariant`, which moves every field that is part of the variant
+    /// from the base into the variant. Retyped fields are converted with their `convert` function
+    /// if set, or with `Into` otherwise. Enum cases are matched by name, such that every case of
+    /// the base must be part of the variant.
+    ///
+    pub fn new(ctx: &'a context::Context, item: &'a ItemContext<'a>, variant: &'a Ident) -> Self {
+        Self {
+            ctx,
+            item,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for FromBaseGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for FromBaseGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let struct_ctx = self
+            .item
+            .struct_ctx
+            .as_ref()
+            .expect("struct context should exist");
+
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+        let variant_ident = context.variant_ident(base_ident);
+
+        let fields = variant_fields(&context, base_ident, &struct_ctx.field_ctxs, &node.fields);
+        let members = fields.iter().map(|field| &field.member);
+        let values = fields.iter().map(|field| {
+            let base_member = &field.base_member;
+            field.convert(parse_quote!(value.#base_member))
+        });
+
+        let (impl_generics, ty_generics, where_clause) = node.generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics From<#base_ident #ty_generics> for #variant_ident #ty_generics #where_clause {
+                fn from(value: #base_ident #ty_generics) -> Self {
+                    Self { #(#members: #values,)* }
+                }
+            }
+        });
+    }
+
+    fn visit_item_enum_mut(&mut self, node: &mut ItemEnum) {
+        let enum_ctx = self
+            .item
+            .enum_ctx
+            .as_ref()
+            .expect("enum context should exist");
+
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+        let variant_ident = context.variant_ident(base_ident);
+
+        let mut arms = Vec::<Arm>::new();
+
+        for case in &node.variants {
+            let variant_case = match expand_case(&context, base_ident, enum_ctx, case) {
+                Some(variant_case) => variant_case,
+                None => {
+                    self.errors.push(Error::new(
+                        case.ident.span(),
+                        format!(
+                            "cannot convert `{}` into `{}`, which lacks the case `{}`",
+                            base_ident, variant_ident, case.ident
+                        ),
+                    ));
+                    continue;
+                }
+            };
+
+            let case_ident = &case.ident;
+            let variant_case_ident = &variant_case.ident;
+
+            if context.is_kind() {
+                arms.push(parse_quote! {
+                    #base_ident::#case_ident { .. } => Self::#variant_case_ident
+                });
+                continue;
+            }
+
+            let has_payload = enum_ctx
+                .case_ctxs
+                .get(case)
+                .and_then(|case_ctx| case_ctx.settings.get(self.variant))
+                .is_some_and(|settings| settings.payload.is_some());
+
+            if has_payload {
+                self.errors.push(Error::new(
+                    case.ident.span(),
+                    format!(
+                        "cannot convert the case `{}` into `{}`, as its payload is replaced",
+                        case.ident, variant_ident
+                    ),
+                ));
+                continue;
+            }
+
+            let fields = variant_fields(&context, base_ident, &enum_ctx.field_ctxs, &case.fields);
+            let base_members = fields.iter().map(|field| &field.base_member);
+            let bindings = fields
+                .iter()
+                .map(|field| field.binding())
+                .collect::<Vec<_>>();
+            let members = fields.iter().map(|field| &field.member);
+            let values = fields
+                .iter()
+                .zip(&bindings)
+                .map(|(field, binding)| field.convert(parse_quote!(#binding)));
+
+            arms.push(parse_quote! {
+                #base_ident::#case_ident { #(#base_members: #bindings,)* .. } => {
+                    Self::#variant_case_ident { #(#members: #values,)* }
+                }
+            });
+        }
+
+        // Kinds have no generic parameters.
+        let variant_generics = match context.is_kind() {
+            true => Generics::default(),
+            false => node.generics.clone(),
+        };
+
+        let (impl_generics, ty_generics, where_clause) = node.generics.split_for_impl();
+        let (_, variant_ty_generics, _) = variant_generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics From<#base_ident #ty_generics> for #variant_ident #variant_ty_generics #where_clause {
+                fn from(value: #base_ident #ty_generics) -> Self {
+                    match value {
+                        #(#arms,)*
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,229 @@
+use proc_macro2::Span;
+use syn::{Error, Ident, Item, visit_mut::VisitMut};
+
+use crate::context::{self, item::ItemContext, variant::VariantContext};
+
+use super::{
+    Generator, accessor::AccessorGenerator, builder::BuilderGenerator, complete::CompleteGenerator,
+    conversions::ConversionsGenerator, default::DefaultGenerator, diff::DiffGenerator,
+    from_base::FromBaseGenerator, kind::KindGenerator, migrate::MigrateGenerator,
+    patch::PatchGenerator, split_cases::SplitCasesGenerator, validate::ValidateGenerator,
+};
+
+pub struct ItemGenerator<'a> {
+    ctx: &'a context::Context,
+    item: &'a ItemContext<'a>,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> ItemGenerator<'a> {
+    /// Generates the items that come along with the variants of an item, such as conversions
+    /// between them. The item is expected to still have its variant attributes, such that its
+    /// nodes match the contexts of `item`.
+    ///
+    pub fn new(ctx: &'a context::Context, item: &'a ItemContext) -> Self {
+        Self {
+            ctx,
+            item,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// The declared variants along with their options, in order of declaration.
+    ///
+    fn variant_ctxs(&self) -> Vec<(&'a Ident, &'a VariantContext)> {
+        self.ctx
+            .variants
+            .iter()
+            .filter_map(|variant| Some((variant, self.ctx.variant_ctxs.get(variant)?)))
+            .collect()
+    }
+
+    /// Run `generator` on the item `node`, and collect the items and errors it generates.
+    ///
+    fn generate(&mut self, node: &mut Item, mut generator: impl Generator) {
+        generator.visit_item_mut(node);
+
+        let (mut items, mut errors) = generator.into_output();
+        self.items.append(&mut items);
+        self.errors.append(&mut errors);
+    }
+
+    /// Push an error for each option that requires the other kind of item, as generators only
+    /// run on the kind of item they support, or that requires an owned variant.
+    ///
+    fn check_item_kind(&mut self, node: &Item) {
+        let is_enum = matches!(node, Item::Enum(_));
+
+        let fields = match node {
+            Item::Struct(node) => node.fields.iter().collect::<Vec<_>>(),
+            Item::Enum(node) => node.variants.iter().flat_map(|case| &case.fields).collect(),
+            _ => Vec::new(),
+        };
+        let field_ctxs = match (&self.item.struct_ctx, &self.item.enum_ctx) {
+            (Some(struct_ctx), _) => Some(&struct_ctx.field_ctxs),
+            (_, Some(enum_ctx)) => Some(&enum_ctx.field_ctxs),
+            _ => None,
+        };
+
+        for (variant, variant_ctx) in self.variant_ctxs() {
+            let (options, kind) = match is_enum {
+                true => (
+                    vec![
+                        ("patch", variant_ctx.patch),
+                        ("diff", variant_ctx.diff),
+                        ("complete", variant_ctx.complete),
+                        ("impl_default", variant_ctx.impl_default),
+                        ("builder", variant_ctx.builder),
+                    ],
+                    "a struct",
+                ),
+                false => (vec![("kind", variant_ctx.kind)], "an enum"),
+            };
+
+            for (option, _) in options.into_iter().filter(|(_, set)| *set) {
+                self.errors.push(Error::new(
+                    variant.span(),
+                    format!("{} variant `{}` requires {}", option, variant, kind),
+                ));
+            }
+
+            // The generated code moves owned values in and out of the fields, which borrowed
+            // variants only hold references to.
+            if variant_ctx.borrowed {
+                let owned = [
+                    ("patch", variant_ctx.patch),
+                    ("diff", variant_ctx.diff),
+                    ("complete", variant_ctx.complete),
+                    ("impl_default", variant_ctx.impl_default),
+                ];
+
+                for (option, _) in owned.into_iter().filter(|(_, set)| *set) {
+                    self.errors.push(Error::new(
+                        variant.span(),
+                        format!(
+                            "variant `{}` cannot be both `{}` and `borrowed`",
+                            variant, option
+                        ),
+                    ));
+                }
+
+                // The fields are only borrowed, whereas projections transform owned values.
+                let projects = fields
+                    .iter()
+                    .filter_map(|field| field_ctxs?.get(field)?.settings.get(variant))
+                    .filter(|settings| settings.include)
+                    .filter_map(|settings| settings.project.as_ref());
+
+                for project in projects {
+                    self.errors.push(Error::new_spanned(
+                        project,
+                        format!(
+                            "the fields of borrowed variant `{}` cannot be projected",
+                            variant
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let (options, kind) = match is_enum {
+            true => (
+                vec![
+                    ("derive_conversions", self.ctx.derive_conversions),
+                    ("versioned", self.ctx.versioned),
+                ],
+                "a struct",
+            ),
+            false => (vec![("split_cases", self.ctx.split_cases)], "an enum"),
+        };
+
+        for (option, _) in options.into_iter().filter(|(_, set)| *set) {
+            self.errors.push(Error::new(
+                Span::call_site(),
+                format!("`{}` requires {}", option, kind),
+            ));
+        }
+    }
+}
+
+impl VisitMut for ItemGenerator<'_> {
+    fn visit_item_mut(&mut self, node: &mut Item) {
+        let ctx = self.ctx;
+        let item = self.item;
+
+        let context_exists = match node {
+            Item::Enum(_) => item.enum_ctx.is_some(),
+            Item::Struct(_) => item.struct_ctx.is_some(),
+            _ => return,
+        };
+
+        if !context_exists {
+            self.errors.push(Error::new(
+                Span::call_site(),
+                "bug: item context should exist",
+            ));
+            return;
+        }
+
+        self.check_item_kind(node);
+
+        if let Some(struct_ctx) = &item.struct_ctx {
+            if ctx.derive_conversions {
+                self.generate(node, ConversionsGenerator::new(ctx, struct_ctx));
+            }
+
+            if ctx.versioned {
+                self.generate(node, MigrateGenerator::new(ctx, struct_ctx));
+            }
+        }
+
+        for (variant, variant_ctx) in self.variant_ctxs() {
+            if variant_ctx.from_base {
+                self.generate(node, FromBaseGenerator::new(ctx, item, variant));
+            }
+
+            if let Some(accessor) = &variant_ctx.accessor {
+                self.generate(node, AccessorGenerator::new(ctx, item, variant, accessor));
+            }
+
+            if let Some(struct_ctx) = &item.struct_ctx {
+                if variant_ctx.patch && !variant_ctx.borrowed {
+                    self.generate(node, PatchGenerator::new(ctx, struct_ctx, variant));
+                }
+
+                if variant_ctx.diff && !variant_ctx.borrowed {
+                    self.generate(node, DiffGenerator::new(ctx, struct_ctx, variant));
+                }
+
+                if variant_ctx.complete && !variant_ctx.borrowed {
+                    self.generate(node, CompleteGenerator::new(ctx, struct_ctx, variant));
+                }
+
+                if variant_ctx.impl_default && !variant_ctx.borrowed {
+                    self.generate(node, DefaultGenerator::new(ctx, struct_ctx, variant));
+                }
+
+                if variant_ctx.builder {
+                    self.generate(node, BuilderGenerator::new(ctx, struct_ctx, variant));
+                }
+            }
+
+            self.generate(node, ValidateGenerator::new(ctx, item, variant));
+
+            if let Some(enum_ctx) = &item.enum_ctx
+                && variant_ctx.kind
+            {
+                self.generate(node, KindGenerator::new(ctx, enum_ctx, variant));
+            }
+        }
+
+        if let Some(enum_ctx) = &item.enum_ctx
+            && ctx.split_cases
+        {
+            self.generate(node, SplitCasesGenerator::new(ctx, enum_ctx));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::iter;
+
+use syn::{Error, Ident, Item, ItemEnum, parse_quote, visit_mut::VisitMut};
+
+use crate::{
+    context::{self, r#enum::EnumContext},
+    expanders::Context,
+};
+
+use super::{Generator, expand_case};
+
+pub struct KindGenerator<'a> {
+    ctx: &'a context::Context,
+    enum_ctx: &'a EnumContext<'a>,
+    kind: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> KindGenerator<'a> {
+    /// Generates `From<&Foo> for FooKind` for the kind variant `kind`, from the base enum and
+    /// from every variant that is not a kind itself. Variants with cases that are not part of the
+    /// kind cannot be converted, and are skipped.
+    ///
+    pub fn new(ctx: &'a context::Context, enum_ctx: &'a EnumContext<'a>, kind: &'a Ident) -> Self {
+        Self {
+            ctx,
+            enum_ctx,
+            kind,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for KindGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for KindGenerator<'_> {
+    fn visit_item_enum_mut(&mut self, node: &mut ItemEnum) {
+        let kind_context = Context::new(self.ctx, Some(self.kind));
+        let kind_ident = kind_context.variant_ident(&node.ident);
+
+        let sources = iter::once(None).chain(
+            self.ctx
+                .variants
+                .iter()
+                .filter(|variant| !Context::new(self.ctx, Some(variant)).is_kind())
+                .map(Some),
+        );
+
+        'sources: for source in sources {
+            let source_context = Context::new(self.ctx, source);
+            let source_ident = source_context.variant_ident(&node.ident);
+            let source_generics = source_context.variant_generics(&node.generics);
+            let (impl_generics, ty_generics, where_clause) = source_generics.split_for_impl();
+
+            let mut source_cases = Vec::new();
+            let mut kind_cases = Vec::new();
+
+            for case in &node.variants {
+                let source_case =
+                    match expand_case(&source_context, &node.ident, self.enum_ctx, case) {
+                        Some(source_case) => source_case,
+                        None => continue,
+                    };
+
+                let kind_case = match expand_case(&kind_context, &node.ident, self.enum_ctx, case) {
+                    Some(kind_case) => kind_case,
+                    None => continue 'sources,
+                };
+
+                source_cases.push(source_case.ident);
+                kind_cases.push(kind_case.ident);
+            }
+
+            self.items.push(parse_quote! {
+                impl #impl_generics From<&#source_ident #ty_generics> for #kind_ident #where_clause {
+                    fn from(value: &#source_ident #ty_generics) -> Self {
+                        match value {
+                            #(#source_ident::#source_cases { .. } => Self::#kind_cases,)*
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use syn::{Error, Expr, Item, ItemStruct, parse_quote, visit_mut::VisitMut};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+};
+
+use super::{Generator, missing_fields, variant_fields};
+
+pub struct MigrateGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> MigrateGenerator<'a> {
+    /// Generates `From<FooV1> for FooV2` for each version and the next, in order of declaration,
+    /// along with `fn migrate_to_latest(self)` on every version. Fields kept by the next version
+    /// are moved, or converted with `Into` when their types differ, and fields added by it take
+    /// the default of their `since(..)`, or `Default::default()`.
+    ///
+    pub fn new(ctx: &'a context::Context, struct_ctx: &'a StructContext<'a>) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for MigrateGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for MigrateGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let contexts = self
+            .ctx
+            .variants
+            .iter()
+            .map(|variant| Context::new(self.ctx, Some(variant)))
+            .collect::<Vec<_>>();
+
+        for context in &contexts {
+            if context.borrow_lifetime().is_some() {
+                let variant = context.variant.expect("versions are variants");
+
+                self.errors.push(Error::new(
+                    variant.span(),
+                    format!("versioned variant `{}` cannot be borrowed", variant),
+                ));
+                return;
+            }
+        }
+
+        let base_ident = &node.ident;
+        let (impl_generics, ty_generics, where_clause) = node.generics.split_for_impl();
+
+        /* The migrations from each version into the next.
+         */
+
+        for pair in contexts.windows(2) {
+            let (previous, next) = (&pair[0], &pair[1]);
+
+            let previous_ident = previous.variant_ident(base_ident);
+            let next_ident = next.variant_ident(base_ident);
+
+            let fields = variant_fields(
+                previous,
+                base_ident,
+                &self.struct_ctx.field_ctxs,
+                &node.fields,
+            );
+            let missing = missing_fields(
+                previous,
+                base_ident,
+                &self.struct_ctx.field_ctxs,
+                &node.fields,
+            );
+            let next_fields =
+                variant_fields(next, base_ident, &self.struct_ctx.field_ctxs, &node.fields);
+
+            let mut members = Vec::new();
+            let mut values = Vec::<Expr>::new();
+
+            for next_field in &next_fields {
+                members.push(&next_field.member);
+
+                let field = fields
+                    .iter()
+                    .find(|field| field.base_member == next_field.base_member);
+
+                values.push(match field {
+                    Some(field) => {
+                        let member = &field.member;
+
+                        match field.field.ty == next_field.field.ty {
+                            true => parse_quote!(value.#member),
+                            false => parse_quote!(::core::convert::Into::into(value.#member)),
+                        }
+                    }
+                    None => missing
+                        .iter()
+                        .find(|field| field.base_member == next_field.base_member)
+                        .and_then(|field| field.settings?.migrate.clone())
+                        .unwrap_or_else(|| parse_quote!(::core::default::Default::default())),
+                });
+            }
+
+            self.items.push(parse_quote! {
+                impl #impl_generics From<#previous_ident #ty_generics> for #next_ident #ty_generics #where_clause {
+                    fn from(value: #previous_ident #ty_generics) -> Self {
+                        Self { #(#members: #values,)* }
+                    }
+                }
+            });
+        }
+
+        /* The migrations from each version into the latest.
+         */
+
+        let latest_ident = match contexts.last() {
+            Some(latest) => latest.variant_ident(base_ident),
+            None => return,
+        };
+
+        for (index, context) in contexts.iter().enumerate() {
+            let variant_ident = context.variant_ident(base_ident);
+            let vis = context.variant_vis(&node.vis);
+
+            let migration =
+                contexts[index + 1..]
+                    .iter()
+                    .fold(parse_quote!(self), |value: Expr, next| {
+                        let next_ident = next.variant_ident(base_ident);
+                        parse_quote!(#next_ident::from(#value))
+                    });
+
+            self.items.push(parse_quote! {
+                impl #impl_generics #variant_ident #ty_generics #where_clause {
+                    #vis fn migrate_to_latest(self) -> #latest_ident #ty_generics {
+                        #migration
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use syn::{Error, Expr, Ident, Item, ItemStruct, Stmt, Type, parse_quote, visit_mut::VisitMut};
+
+use crate::{
+    context::{self, r#struct::StructContext},
+    expanders::Context,
+    utilities::type_ext::TypeExt,
+};
+
+use super::{Generator, VariantField, variant_fields};
+
+pub struct PatchGenerator<'a> {
+    ctx: &'a context::Context,
+    struct_ctx: &'a StructContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> PatchGenerator<'a> {
+    /// Generates `fn apply(self, target: &mut Foo)` on the patch variant `variant`, which sets the
+    /// fields of `target` to those of the patch, as described by `PatchField`.
+    ///
+    pub fn new(
+        ctx: &'a context::Context,
+        struct_ctx: &'a StructContext<'a>,
+        variant: &'a Ident,
+    ) -> Self {
+        Self {
+            ctx,
+            struct_ctx,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for PatchGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for PatchGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+        let variant_ident = context.variant_ident(base_ident);
+
+        let fields = variant_fields(
+            &context,
+            base_ident,
+            &self.struct_ctx.field_ctxs,
+            &node.fields,
+        );
+        let mut stmts = Vec::<Stmt>::new();
+
+        for field in &fields {
+            let member = &field.member;
+            let base_member = &field.base_member;
+
+            match PatchField::new(field, base_ident, &variant_ident, &mut self.errors) {
+                Some(PatchField::Always) => {
+                    stmts.push(parse_quote!(target.#base_member = self.#member;));
+                }
+                Some(PatchField::Optional) => stmts.push(parse_quote! {
+                    if let Some(value) = self.#member {
+                        target.#base_member = value;
+                    }
+                }),
+                Some(PatchField::Nested(wrappers, optional)) => {
+                    let value = match optional {
+                        true => parse_quote!(value),
+                        false => parse_quote!(self.#member),
+                    };
+                    let apply =
+                        apply_nested(&wrappers, value, parse_quote!(&mut target.#base_member));
+
+                    stmts.push(match optional {
+                        true => parse_quote! {
+                            if let Some(value) = self.#member {
+                                #apply;
+                            }
+                        },
+                        false => parse_quote!(#apply;),
+                    });
+                }
+                None => {}
+            }
+        }
+
+        let vis = context.variant_vis(&node.vis);
+
+        let variant_generics = context.variant_generics(&node.generics);
+        let (impl_generics, variant_ty_generics, where_clause) = variant_generics.split_for_impl();
+        let (_, ty_generics, _) = node.generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #variant_ident #variant_ty_generics #where_clause {
+                #vis fn apply(self, target: &mut #base_ident #ty_generics) {
+                    #(#stmts)*
+                }
+            }
+        });
+    }
+}
+
+/// How a field of a patch variant relates to its base field.
+///
+pub enum PatchField {
+    /// A field that is not retyped, and is always set.
+    Always,
+
+    /// A field retyped as `Option<{}>`, which is only set when it is `Some`, such that fields of
+    /// type `Option<T>` can be cleared with `Some(None)`.
+    Optional,
+
+    /// A field retyped with `{b}{v}`, such as `Option<Box<{b}{v}>>`, which is patched recursively
+    /// through the `Box` and `Option` wrappers it has in common with the base field, the latter
+    /// only when the target is `Some`. The flag tells whether the patch has an additional
+    /// `Option`, as `Option<Box<{b}{v}>>` has for `Box<Foo>`, which is only applied when `Some`.
+    Nested(Vec<Wrapper>, bool),
+}
+
+#[derive(Clone, Copy)]
+pub enum Wrapper {
+    Box,
+    Option,
+}
+
+impl PatchField {
+    /// Determine how `field` is patched, or push an error if it cannot be.
+    ///
+    pub fn new(
+        field: &VariantField,
+        base_ident: &Ident,
+        variant_ident: &Ident,
+        errors: &mut Vec<Error>,
+    ) -> Option<Self> {
+        let retype = match field.settings.and_then(|settings| settings.retype.as_ref()) {
+            Some(retype) => retype,
+            None => return Some(Self::Always),
+        };
+
+        let patch_ty = &field.field.ty;
+        let base_ty = &field.base_ty;
+
+        let patch_field = if retype.value().contains("{b}{v}") {
+            wrappers(patch_ty, base_ty, base_ident, variant_ident)
+                .map(|wrappers| Self::Nested(wrappers, false))
+                .or_else(|| {
+                    let patch_ty = patch_ty.unwrap_generic("Option")?;
+                    let wrappers = wrappers(patch_ty, base_ty, base_ident, variant_ident)?;
+                    Some(Self::Nested(wrappers, true))
+                })
+        } else {
+            patch_ty
+                .unwrap_generic("Option")
+                .filter(|patch_ty| *patch_ty == base_ty)
+                .map(|_| Self::Optional)
+        };
+
+        if patch_field.is_none() {
+            errors.push(Error::new(
+                retype.span(),
+                "cannot patch a field with this retype, expected `Option<{}>` or a retype with `{b}{v}`",
+            ));
+        }
+
+        patch_field
+    }
+}
+
+/// Find the `Box` and `Option` wrappers, outermost first, around the variant `variant_ident` in
+/// `patch_ty` and around the base `base_ident` in `base_ty`, if they are the same.
+///
+fn wrappers(
+    patch_ty: &Type,
+    base_ty: &Type,
+    base_ident: &Ident,
+    variant_ident: &Ident,
+) -> Option<Vec<Wrapper>> {
+    for (wrapper, name) in [(Wrapper::Box, "Box"), (Wrapper::Option, "Option")] {
+        if let (Some(patch_ty), Some(base_ty)) =
+            (patch_ty.unwrap_generic(name), base_ty.unwrap_generic(name))
+        {
+            let mut wrappers = wrappers(patch_ty, base_ty, base_ident, variant_ident)?;
+            wrappers.insert(0, wrapper);
+            return Some(wrappers);
+        }
+    }
+
+    match (patch_ty, base_ty) {
+        (Type::Path(patch_ty), Type::Path(base_ty))
+            if patch_ty.path.segments.last()?.ident == *variant_ident
+                && base_ty.path.segments.last()?.ident == *base_ident =>
+        {
+            Some(Vec::new())
+        }
+        _ => None,
+    }
+}
+
+/// Create the expression applying the patch `value` onto `target`, a mutable reference to the
+/// base, through `wrappers`.
+///
+fn apply_nested(wrappers: &[Wrapper], value: Expr, target: Expr) -> Expr {
+    match wrappers.split_first() {
+        None => parse_quote!((#value).apply(#target)),
+        Some((Wrapper::Box, wrappers)) => apply_nested(
+            wrappers,
+            parse_quote!(*#value),
+            parse_quote!(&mut **#target),
+        ),
+        Some((Wrapper::Option, wrappers)) => {
+            let apply = apply_nested(wrappers, parse_quote!(value), parse_quote!(target));
+
+            parse_quote! {
+                if let (Some(value), Some(target)) = (#value, (#target).as_mut()) {
+                    #apply
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::{collections::HashSet, iter};
+
+use quote::ToTokens;
+use syn::{
+    Attribute, Error, Fields, Generics, Ident, Item, ItemEnum, ItemStruct, Token, Visibility,
+    parse_quote, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{self, r#enum::EnumContext},
+    expanders::Context,
+    utilities::{attribute_remover::AttributeRemover, generics_ext::GenericsExt},
+};
+
+use super::{Generator, binding, expand_case};
+
+pub struct SplitCasesGenerator<'a> {
+    ctx: &'a context::Context,
+    enum_ctx: &'a EnumContext<'a>,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> SplitCasesGenerator<'a> {
+    /// Generates a struct for each enum case, such as `FooCreate` for `Foo::Create`, with
+    /// `From<FooCreate> for Foo` and `TryFrom<Foo> for FooCreate`. The cases of the base enum and
+    /// of every variant that is not a kind are split, such that the structs of a variant, such
+    /// as `FooSummaryCreate`, only have the fields of that variant. The structs are named following
+    /// the naming template, and only take the attributes of the enum that apply to any item, such
+    /// as `#[derive(..)]`.
+    ///
+    pub fn new(ctx: &'a context::Context, enum_ctx: &'a EnumContext<'a>) -> Self {
+        Self {
+            ctx,
+            enum_ctx,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+impl Generator for SplitCasesGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for SplitCasesGenerator<'_> {
+    fn visit_item_enum_mut(&mut self, node: &mut ItemEnum) {
+        let sources = iter::once(None).chain(self.ctx.variants.iter().map(Some));
+
+        // The names of the base and variant items, along with those of the structs generated so
+        // far, which the names of the structs must not collide with.
+        let mut names = sources
+            .clone()
+            .map(|source| Context::new(self.ctx, source).variant_ident(&node.ident))
+            .collect::<HashSet<_>>();
+
+        for source in sources {
+            let context = Context::new(self.ctx, source);
+
+            if context.is_kind() {
+                continue;
+            }
+
+            let enum_ident = context.variant_ident(&node.ident);
+
+            let vis = context.variant_vis(&node.vis);
+
+            let mut attrs = node.attrs.clone();
+
+            if let Some(variant_ctx) = context.variant_ctx() {
+                attrs.extend(variant_ctx.attrs.iter().cloned());
+            }
+
+            attrs.retain(|attr| {
+                ["derive", "cfg", "allow", "expect", "warn", "deny"]
+                    .iter()
+                    .any(|name| attr.path().is_ident(name))
+            });
+
+            let enum_generics = context.variant_generics(&node.generics);
+            let (impl_generics, ty_generics, where_clause) = enum_generics.split_for_impl();
+
+            for case in &node.variants {
+                let mut case = match expand_case(&context, &node.ident, self.enum_ctx, case) {
+                    Some(case) => case,
+                    None => continue,
+                };
+
+                AttributeRemover::new().visit_fields_mut(&mut case.fields);
+
+                for field in case.fields.iter_mut() {
+                    field.vis = vis.clone();
+                }
+
+                let case_ident = &case.ident;
+                let struct_ident = self.ctx.naming.compose(&enum_ident, case_ident);
+
+                if !names.insert(struct_ident.clone()) {
+                    self.errors.push(Error::new(
+                        case_ident.span(),
+                        format!(
+                            "cannot split the case `{}` of `{}` into `{}`, which is already the \
+                             name of another item",
+                            case_ident, enum_ident, struct_ident
+                        ),
+                    ));
+                    continue;
+                }
+
+                let mut generics = enum_generics.clone();
+                generics.retain_used(&case.fields.to_token_stream());
+                let (_, struct_ty_generics, _) = generics.split_for_impl();
+
+                let members = case.fields.members().collect::<Vec<_>>();
+                let bindings = members.iter().map(binding).collect::<Vec<_>>();
+
+                self.items.push(Item::Struct(split_struct(
+                    &attrs,
+                    &vis,
+                    &struct_ident,
+                    &generics,
+                    case.fields,
+                )));
+
+                self.items.push(parse_quote! {
+                    impl #impl_generics From<#struct_ident #struct_ty_generics> for #enum_ident #ty_generics #where_clause {
+                        fn from(
+                            #struct_ident { #(#members: #bindings,)* }: #struct_ident #struct_ty_generics,
+                        ) -> Self {
+                            Self::#case_ident { #(#members: #bindings,)* }
+                        }
+                    }
+                });
+
+                self.items.push(parse_quote! {
+                    impl #impl_generics TryFrom<#enum_ident #ty_generics> for #struct_ident #struct_ty_generics #where_clause {
+                        type Error = #enum_ident #ty_generics;
+
+                        fn try_from(value: #enum_ident #ty_generics) -> Result<Self, Self::Error> {
+                            #[allow(unreachable_patterns)]
+                            match value {
+                                #enum_ident::#case_ident { #(#members: #bindings,)* } => {
+                                    Ok(Self { #(#members: #bindings,)* })
+                                }
+                                value => Err(value),
+                            }
+                        }
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn split_struct(
+    attrs: &[Attribute],
+    vis: &Visibility,
+    ident: &Ident,
+    generics: &Generics,
+    fields: Fields,
+) -> ItemStruct {
+    let semi_token = match &fields {
+        Fields::Named(_) => None,
+        _ => Some(<Token![;]>::default()),
+    };
+
+    ItemStruct {
+        attrs: attrs.to_vec(),
+        vis: vis.clone(),
+        struct_token: Default::default(),
+        ident: ident.clone(),
+        generics: generics.clone(),
+        fields,
+        semi_token,
+    }
+}
@@ -0,0 +1,218 @@
+use quote::ToTokens;
+use syn::{
+    Arm, Error, Expr, Generics, Ident, Item, ItemEnum, ItemStruct, LitStr, Stmt, Type, Visibility,
+    parse_quote, spanned::Spanned, visit_mut::VisitMut,
+};
+
+use crate::{
+    context::{self, item::ItemContext},
+    expanders::Context,
+};
+
+use super::{Generator, VariantField, expand_case, variant_fields};
+
+pub struct ValidateGenerator<'a> {
+    ctx: &'a context::Context,
+    item: &'a ItemContext<'a>,
+    variant: &'a Ident,
+    pub items: Vec<Item>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> ValidateGenerator<'a> {
+    /// Generates `fn validate(&self) -> Result<(), Vec<(&'static str, E)>>` on `variant` when any
+    /// of its fields has a `validate` function, which calls every validator with a reference to
+    /// its field and collects the errors along with the names of the fields. `E` is the
+    /// `validate_error` of the variant.
+    ///
+    pub fn new(ctx: &'a context::Context, item: &'a ItemContext<'a>, variant: &'a Ident) -> Self {
+        Self {
+            ctx,
+            item,
+            variant,
+            items: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// The error type of the validators of the variant, or an error if it is not declared.
+    ///
+    fn validate_error(&mut self) -> Option<&'a Type> {
+        let validate_error = self
+            .ctx
+            .variant_ctxs
+            .get(self.variant)
+            .and_then(|variant_ctx| variant_ctx.validate_error.as_ref());
+
+        if validate_error.is_none() {
+            self.errors.push(Error::new(
+                self.variant.span(),
+                format!(
+                    "variant `{}` has field validators, but no error type, as in `{}(validate_error = \"Error\")`",
+                    self.variant, self.variant
+                ),
+            ));
+        }
+
+        validate_error
+    }
+
+    /// Push the `validate` method of the variant, with the statements `body` pushing the errors
+    /// of its fields onto `errors`.
+    ///
+    fn push_validate(
+        &mut self,
+        context: &Context,
+        base_ident: &Ident,
+        vis: &Visibility,
+        generics: &Generics,
+        body: Vec<Stmt>,
+    ) {
+        let validate_error = match self.validate_error() {
+            Some(validate_error) => validate_error,
+            None => return,
+        };
+
+        let variant_ident = context.variant_ident(base_ident);
+        let vis = context.variant_vis(vis);
+
+        let variant_generics = context.variant_generics(generics);
+        let (impl_generics, ty_generics, where_clause) = variant_generics.split_for_impl();
+
+        self.items.push(parse_quote! {
+            impl #impl_generics #variant_ident #ty_generics #where_clause {
+                #vis fn validate(&self) -> ::core::result::Result<(), ::std::vec::Vec<(&'static str, #validate_error)>> {
+                    let mut errors = ::std::vec::Vec::new();
+                    #(#body)*
+
+                    match errors.is_empty() {
+                        true => ::core::result::Result::Ok(()),
+                        false => ::core::result::Result::Err(errors),
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl Generator for ValidateGenerator<'_> {
+    fn into_output(self) -> (Vec<Item>, Vec<Error>) {
+        (self.items, self.errors)
+    }
+}
+
+impl VisitMut for ValidateGenerator<'_> {
+    fn visit_item_struct_mut(&mut self, node: &mut ItemStruct) {
+        let struct_ctx = self
+            .item
+            .struct_ctx
+            .as_ref()
+            .expect("struct context should exist");
+
+        let context = Context::new(self.ctx, Some(self.variant));
+        let fields = variant_fields(&context, &node.ident, &struct_ctx.field_ctxs, &node.fields);
+
+        let stmts = fields
+            .iter()
+            .filter_map(|field| {
+                let member = &field.member;
+                validation(field, parse_quote!(&self.#member))
+            })
+            .collect::<Vec<_>>();
+
+        if stmts.is_empty() {
+            return;
+        }
+
+        self.push_validate(&context, &node.ident, &node.vis, &node.generics, stmts);
+    }
+
+    fn visit_item_enum_mut(&mut self, node: &mut ItemEnum) {
+        let enum_ctx = self
+            .item
+            .enum_ctx
+            .as_ref()
+            .expect("enum context should exist");
+
+        let context = Context::new(self.ctx, Some(self.variant));
+        let base_ident = &node.ident;
+
+        let mut arms = Vec::<Arm>::new();
+
+        for case in &node.variants {
+            let variant_case = match expand_case(&context, base_ident, enum_ctx, case) {
+                Some(variant_case) => variant_case,
+                None => continue,
+            };
+
+            // Cases with a replaced payload no longer have the fields of the base.
+            let has_payload = enum_ctx
+                .case_ctxs
+                .get(case)
+                .and_then(|case_ctx| case_ctx.settings.get(self.variant))
+                .is_some_and(|settings| settings.payload.is_some());
+
+            if has_payload || context.is_kind() {
+                continue;
+            }
+
+            let fields = variant_fields(&context, base_ident, &enum_ctx.field_ctxs, &case.fields);
+
+            let mut members = Vec::new();
+            let mut bindings = Vec::new();
+            let mut stmts = Vec::new();
+
+            for field in &fields {
+                let binding = field.binding();
+
+                if let Some(stmt) = validation(field, parse_quote!(#binding)) {
+                    members.push(&field.member);
+                    bindings.push(binding);
+                    stmts.push(stmt);
+                }
+            }
+
+            if stmts.is_empty() {
+                continue;
+            }
+
+            let variant_case_ident = &variant_case.ident;
+
+            arms.push(parse_quote! {
+                Self::#variant_case_ident { #(#members: #bindings,)* .. } => {
+                    #(#stmts)*
+                }
+            });
+        }
+
+        if arms.is_empty() {
+            return;
+        }
+
+        let body = parse_quote! {
+            #[allow(unreachable_patterns)]
+            match self {
+                #(#arms,)*
+                _ => {}
+            }
+        };
+
+        self.push_validate(&context, base_ident, &node.vis, &node.generics, vec![body]);
+    }
+}
+
+/// The statement validating `field`, whose value is referenced by `value`, if it has a validator.
+///
+fn validation(field: &VariantField, value: Expr) -> Option<Stmt> {
+    let validate = field.settings?.validate.as_ref()?;
+    let name = LitStr::new(
+        &field.member.to_token_stream().to_string(),
+        field.member.span(),
+    );
+
+    Some(parse_quote! {
+        if let ::core::result::Result::Err(error) = #validate(#value) {
+            errors.push((#name, error));
+        }
+    })
+}
@@ -1,4 +1,5 @@
 pub mod context;
 pub mod expand;
 pub mod expanders;
+pub mod generators;
 pub mod utilities;
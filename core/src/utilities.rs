@@ -1,7 +1,9 @@
 pub mod attribute_remover;
 pub mod errors_ext;
 pub mod fields_ext;
+pub mod generics_ext;
 pub mod ident_ext;
+pub mod item_ext;
 pub mod path_ext;
 pub mod type_ext;
 pub mod vec_ext;
@@ -1,15 +1,18 @@
 use syn::{Error, Ident};
 
+use crate::context::Context;
+
 pub trait ErrorsExt {
     /// Combine all generated errors into a single error object. Each individual error will still be
     /// visible in the output stream.
     ///
     fn combine(&self) -> Option<Error>;
 
-    /// Check of the existance of `variant` in `variants` and generate a standard error message
-    /// when this is not the case. This functionality is used often throughout the library.
+    /// Check of the existance of `variant` in the variants or groups declared in `context` and
+    /// generate a standard error message when this is not the case. This functionality is used
+    /// often throughout the library.
     ///
-    fn contains_variant(&mut self, variants: &Vec<Ident>, variant: &Ident) -> bool;
+    fn contains_variant(&mut self, context: &Context, variant: &Ident) -> bool;
 }
 
 impl ErrorsExt for Vec<Error> {
@@ -30,13 +33,14 @@ impl ErrorsExt for Vec<Error> {
         }))
     }
 
-    fn contains_variant(&mut self, variants: &Vec<Ident>, variant: &Ident) -> bool {
-        let has_variant = variants.iter().any(|v| v == variant);
+    fn contains_variant(&mut self, context: &Context, variant: &Ident) -> bool {
+        let has_variant =
+            context.variants.iter().any(|v| v == variant) || context.groups.contains_key(variant);
 
-        if !variants.iter().any(|v| v == variant) {
+        if !has_variant {
             self.push(Error::new(
                 variant.span(),
-                format!("`{}` has not been declared as a variant", variant),
+                format!("`{}` has not been declared as a variant or group", variant),
             ));
         }
 
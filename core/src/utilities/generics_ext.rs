@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{GenericParam, Generics, Ident};
+
+pub trait GenericsExt {
+    /// Remove the generic parameters that are not used in `tokens`, along with the where clause
+    /// predicates that refer to them.
+    ///
+    fn retain_used(&mut self, tokens: &TokenStream);
+}
+
+impl GenericsExt for Generics {
+    fn retain_used(&mut self, tokens: &TokenStream) {
+        let used = idents(tokens);
+
+        let removed = self
+            .params
+            .iter()
+            .map(param_ident)
+            .filter(|ident| !used.contains(ident))
+            .collect::<HashSet<_>>();
+
+        if removed.is_empty() {
+            return;
+        }
+
+        self.params = std::mem::take(&mut self.params)
+            .into_iter()
+            .filter(|param| !removed.contains(&param_ident(param)))
+            .collect();
+
+        if let Some(where_clause) = &mut self.where_clause {
+            where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+                .into_iter()
+                .filter(|predicate| idents(&predicate.to_token_stream()).is_disjoint(&removed))
+                .collect();
+        }
+
+        if self.params.is_empty() {
+            self.lt_token = None;
+            self.gt_token = None;
+        }
+    }
+}
+
+fn param_ident(param: &GenericParam) -> Ident {
+    match param {
+        GenericParam::Lifetime(param) => param.lifetime.ident.clone(),
+        GenericParam::Type(param) => param.ident.clone(),
+        GenericParam::Const(param) => param.ident.clone(),
+    }
+}
+
+fn idents(tokens: &TokenStream) -> HashSet<Ident> {
+    let mut idents = HashSet::new();
+
+    for token in tokens.clone() {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident);
+            }
+            TokenTree::Group(group) => idents.extend(self::idents(&group.stream())),
+            _ => {}
+        }
+    }
+
+    idents
+}
@@ -1,5 +1,7 @@
 use syn::{Ident, Path, PathArguments, PathSegment, Type, TypePath, punctuated::Punctuated};
 
+use crate::context::naming::Naming;
+
 pub trait IdentExt: Sized {
     /// Transform an identifier into a path.
     ///
@@ -23,6 +25,10 @@ pub trait IdentExt: Sized {
     /// Create a new ident with the text of another identifier appended to `self`.
     ///
     fn from_appendix(&self, appendix: &Self) -> Self;
+
+    /// Create the identifier of the item generated for `variant`, following `naming`.
+    ///
+    fn to_variant(&self, naming: &Naming, variant: &Ident) -> Self;
 }
 
 impl IdentExt for Ident {
@@ -43,4 +49,8 @@ impl IdentExt for Ident {
     fn from_appendix(&self, appendix: &Self) -> Self {
         Ident::new(&format!("{}{}", self, appendix), appendix.span())
     }
+
+    fn to_variant(&self, naming: &Naming, variant: &Ident) -> Self {
+        naming.ident(self, variant)
+    }
 }
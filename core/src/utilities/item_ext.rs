@@ -0,0 +1,30 @@
+use syn::{Attribute, Item, Visibility};
+
+pub trait ItemExt {
+    /// Get the attributes of the item, if it is of a supported kind.
+    ///
+    fn attrs_mut(&mut self) -> Option<&mut Vec<Attribute>>;
+
+    /// Get the visibility of the item, if it is of a kind that has one.
+    ///
+    fn vis_mut(&mut self) -> Option<&mut Visibility>;
+}
+
+impl ItemExt for Item {
+    fn attrs_mut(&mut self) -> Option<&mut Vec<Attribute>> {
+        match self {
+            Item::Enum(item) => Some(&mut item.attrs),
+            Item::Impl(item) => Some(&mut item.attrs),
+            Item::Struct(item) => Some(&mut item.attrs),
+            _ => None,
+        }
+    }
+
+    fn vis_mut(&mut self) -> Option<&mut Visibility> {
+        match self {
+            Item::Enum(item) => Some(&mut item.vis),
+            Item::Struct(item) => Some(&mut item.vis),
+            _ => None,
+        }
+    }
+}
@@ -1,21 +1,23 @@
 use syn::{Ident, Path};
 
+use crate::context::naming::Naming;
+
 use super::ident_ext::IdentExt;
 
 pub trait PathExt: Sized {
-    /// Create a new path with the text of an identifier appended to the last path segment of `self`.
+    /// Create a new path with the last path segment of `self` replaced by the identifier of the
+    /// item generated for `variant`, following `naming`.
     ///
-    fn from_appendix(&self, appendix: &Ident) -> Self;
+    fn to_variant(&self, naming: &Naming, variant: &Ident) -> Self;
 }
 
 impl PathExt for Path {
-    fn from_appendix(&self, appendix: &Ident) -> Self {
+    fn to_variant(&self, naming: &Naming, variant: &Ident) -> Self {
         let mut self_cpy = self.clone();
 
-        self_cpy
-            .segments
-            .last_mut()
-            .map(|segment| segment.ident = segment.ident.from_appendix(appendix));
+        if let Some(segment) = self_cpy.segments.last_mut() {
+            segment.ident = segment.ident.to_variant(naming, variant);
+        }
 
         self_cpy
     }
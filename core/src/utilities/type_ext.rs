@@ -1,4 +1,6 @@
-use syn::{Ident, Type, TypePath};
+use syn::{GenericArgument, Ident, PathArguments, Type, TypePath};
+
+use crate::context::naming::Naming;
 
 use super::path_ext::PathExt;
 
@@ -7,10 +9,10 @@ pub trait TypePathExt: Sized {
     ///
     fn into_type(self) -> Type;
 
-    /// Create a new type path with the text of an identifier appended to the last path segment of
-    /// `self`.
+    /// Create a new type path with the last path segment of `self` replaced by the identifier of
+    /// the item generated for `variant`, following `naming`.
     ///
-    fn from_appendix(&self, appendix: &Ident) -> Self;
+    fn to_variant(&self, naming: &Naming, variant: &Ident) -> Self;
 }
 
 impl TypePathExt for TypePath {
@@ -18,9 +20,39 @@ impl TypePathExt for TypePath {
         Type::Path(self)
     }
 
-    fn from_appendix(&self, appendix: &Ident) -> Self {
+    fn to_variant(&self, naming: &Naming, variant: &Ident) -> Self {
         let mut self_cpy = self.clone();
-        self_cpy.path = self_cpy.path.from_appendix(appendix);
+        self_cpy.path = self_cpy.path.to_variant(naming, variant);
         self_cpy
     }
 }
+
+pub trait TypeExt {
+    /// Get the type wrapped by `self` if it is the generic type `wrapper` with a single type
+    /// argument, such as `T` in `Option<T>`.
+    ///
+    fn unwrap_generic(&self, wrapper: &str) -> Option<&Type>;
+}
+
+impl TypeExt for Type {
+    fn unwrap_generic(&self, wrapper: &str) -> Option<&Type> {
+        let segment = match self {
+            Type::Path(ty_path) if ty_path.qself.is_none() => ty_path.path.segments.last()?,
+            _ => return None,
+        };
+
+        if segment.ident != wrapper {
+            return None;
+        }
+
+        let arguments = match &segment.arguments {
+            PathArguments::AngleBracketed(arguments) if arguments.args.len() == 1 => arguments,
+            _ => return None,
+        };
+
+        match arguments.args.first()? {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }
+    }
+}
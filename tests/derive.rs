@@ -40,6 +40,22 @@ mod tests {
         };
     }
 
+    #[test]
+    fn derive_variant_attributes() {
+        #[variants(Summary(derive(Debug)), Update(derive(Clone, Default)))]
+        #[derive(PartialEq)]
+        struct Foo {
+            #[variants(include(Summary, Update))]
+            bar: usize,
+        }
+
+        let summary = FooSummary { bar: 1 };
+        let update = FooUpdate::default();
+
+        assert_eq!(format!("{:?}", summary), "FooSummary { bar: 1 }");
+        assert!(update.clone() == FooUpdate { bar: 0 });
+    }
+
     #[test]
     fn derive_impl() {
         #[variants(Summary)]
@@ -65,6 +81,36 @@ mod tests {
         let _ = FooSummary::new();
     }
 
+    #[test]
+    fn derive_impl_inheritance() {
+        #[variants(Summary, Detail: Summary)]
+        #[allow(dead_code)]
+        struct Foo {
+            #[variants(include(Summary))]
+            id: usize,
+            #[variants(include(Detail))]
+            name: String,
+            bar: String,
+        }
+
+        #[variants(Summary, Detail: Summary)]
+        impl Foo {
+            fn new() -> Self {
+                #[variants(include(Summary), vary_type)]
+                Foo {
+                    #[variants(include(Summary))]
+                    id: 0,
+                    #[variants(include(Detail))]
+                    name: "hola".into(),
+                    bar: "mundo".into(),
+                }
+            }
+        }
+
+        assert_eq!(FooDetail::new().name, "hola");
+        assert_eq!(FooSummary::new().id, 0);
+    }
+
     #[test]
     fn derive_impl_trait() {
         trait Hello {
@@ -222,4 +268,352 @@ mod tests {
         let _: Foo = Foo::new();
         let _: FooSummary = FooSummary::new();
     }
+
+    #[test]
+    fn derive_split_cases() {
+        #[variants(Summary, Kind(kind), split_cases)]
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            Create {
+                #[variants(include(Summary))]
+                id: usize,
+                name: String,
+            },
+            Delete(#[variants(include(Summary))] usize),
+        }
+
+        let create = FooCreate {
+            id: 1,
+            name: "hi".into(),
+        };
+        let foo = Foo::from(create);
+
+        assert_eq!(FooKind::from(&foo), FooKind::Create);
+        assert!(FooDelete::try_from(foo).is_err());
+        assert_eq!(
+            FooSummaryDelete::try_from(FooSummary::Delete(2)),
+            Ok(FooSummaryDelete(2))
+        );
+    }
+
+    #[test]
+    fn derive_from_base() {
+        #[variants(Summary(from_base), default = include)]
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            Created {
+                #[variants(exclude(Summary))]
+                secret: String,
+                #[variants(include(Summary), retype = "u64")]
+                id: u32,
+            },
+            #[variants(include(Summary), rename = "Gone")]
+            Deleted(#[variants(exclude(Summary))] String, u32),
+        }
+
+        let created = Foo::Created {
+            secret: "shh".into(),
+            id: 1,
+        };
+        let deleted = Foo::Deleted("shh".into(), 2);
+
+        assert_eq!(FooSummary::from(created), FooSummary::Created { id: 1 });
+        assert_eq!(FooSummary::from(deleted), FooSummary::Gone(2));
+    }
+
+    #[test]
+    fn derive_project() {
+        fn mask(email: String) -> String {
+            match email.split_once('@') {
+                Some((_, domain)) => format!("***@{}", domain),
+                None => "***".into(),
+            }
+        }
+
+        fn truncate(description: String) -> String {
+            description.chars().take(4).collect()
+        }
+
+        #[variants(Public(from_base), Internal(from_base))]
+        #[derive(Debug, PartialEq)]
+        struct Foo {
+            #[variants(include(Public), project = mask)]
+            #[variants(include(Internal))]
+            email: String,
+
+            #[variants(include(Public), retype = "Box<str>", project = truncate, convert = Box::from)]
+            description: String,
+        }
+
+        let foo = || Foo {
+            email: "me@example.com".into(),
+            description: "long description".into(),
+        };
+
+        assert_eq!(
+            FooPublic::from(foo()),
+            FooPublic {
+                email: "***@example.com".into(),
+                description: "long".into(),
+            }
+        );
+        assert_eq!(
+            FooInternal::from(foo()),
+            FooInternal {
+                email: "me@example.com".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn derive_impl_default() {
+        #[derive(Debug, PartialEq)]
+        struct Id(u32);
+
+        #[variants(Summary(impl_default), Update(impl_default))]
+        #[derive(Debug, PartialEq)]
+        struct Foo {
+            id: Id,
+
+            #[variants(include(Summary), default = "untitled".into())]
+            #[variants(include(Update), retype = "Option<{}>")]
+            name: String,
+
+            #[variants(include(Summary, Update), default = 10)]
+            limit: u32,
+        }
+
+        assert_eq!(
+            FooSummary::default(),
+            FooSummary {
+                name: "untitled".into(),
+                limit: 10,
+            }
+        );
+        assert_eq!(
+            FooUpdate::default(),
+            FooUpdate {
+                name: None,
+                limit: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn derive_borrowed() {
+        #[variants(DetailView(borrowed(as_view)), default = include)]
+        #[derive(Debug, PartialEq)]
+        enum Foo {
+            Created {
+                #[variants(include(DetailView), retype = "str")]
+                name: String,
+                #[variants(exclude(DetailView))]
+                secret: String,
+            },
+            Deleted(usize),
+        }
+
+        let foo = Foo::Created {
+            name: "hi".into(),
+            secret: "shh".into(),
+        };
+
+        assert_eq!(foo.as_view(), FooDetailView::Created { name: "hi" });
+        assert_eq!(Foo::Deleted(1).as_view(), FooDetailView::Deleted(&1));
+    }
+
+    #[test]
+    fn derive_patch() {
+        #[variants(Update(patch, derive(Default)))]
+        #[derive(Debug, PartialEq)]
+        struct Foo {
+            id: usize,
+
+            #[variants(include(Update), retype = "Option<{}>")]
+            name: String,
+
+            #[variants(include(Update), retype = "Option<{}>")]
+            note: Option<String>,
+
+            #[variants(include(Update), retype = "Option<Box<{b}{v}>>")]
+            parent: Option<Box<Foo>>,
+        }
+
+        let mut foo = Foo {
+            id: 1,
+            name: "child".into(),
+            note: Some("note".into()),
+            parent: Some(Box::new(Foo {
+                id: 0,
+                name: "parent".into(),
+                note: None,
+                parent: None,
+            })),
+        };
+
+        FooUpdate {
+            name: Some("renamed".into()),
+            note: Some(None),
+            parent: Some(Box::new(FooUpdate {
+                note: Some(Some("added".into())),
+                ..Default::default()
+            })),
+        }
+        .apply(&mut foo);
+
+        assert_eq!(
+            foo,
+            Foo {
+                id: 1,
+                name: "renamed".into(),
+                note: None,
+                parent: Some(Box::new(Foo {
+                    id: 0,
+                    name: "parent".into(),
+                    note: Some("added".into()),
+                    parent: None,
+                })),
+            }
+        );
+    }
+
+    #[test]
+    fn derive_diff() {
+        #[variants(Update(patch, diff))]
+        #[derive(Clone, Debug, PartialEq)]
+        struct Foo {
+            #[variants(include(Update), retype = "Option<{}>")]
+            name: String,
+
+            #[variants(include(Update), retype = "Option<{}>")]
+            note: Option<String>,
+
+            #[variants(include(Update), retype = "Option<{}>")]
+            parent: Option<Box<Foo>>,
+        }
+
+        let old = Foo {
+            name: "child".into(),
+            note: Some("note".into()),
+            parent: Some(Box::new(Foo {
+                name: "parent".into(),
+                note: None,
+                parent: None,
+            })),
+        };
+
+        let mut new = old.clone();
+        new.note = None;
+        new.parent.as_mut().unwrap().name = "renamed".into();
+
+        let update = FooUpdate::diff(&old, &new);
+
+        assert_eq!(update.name, None);
+        assert_eq!(update.note, Some(None));
+        assert_eq!(update.parent, Some(new.parent.clone()));
+
+        let mut patched = old.clone();
+        update.apply(&mut patched);
+
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn derive_conversions() {
+        #[variants(Detail, Summary, derive_conversions(Detail -> Summary))]
+        #[derive(Debug, PartialEq)]
+        struct Foo {
+            #[variants(include(Detail, Summary))]
+            id: u32,
+            #[variants(include(Detail))]
+            name: String,
+        }
+
+        let detail = FooDetail {
+            id: 1,
+            name: "hi".into(),
+        };
+
+        assert_eq!(FooSummary::from(detail), FooSummary { id: 1 });
+    }
+
+    #[test]
+    fn derive_versioned() {
+        #[variants(V1, V2, V3, versioned)]
+        #[derive(Debug, PartialEq)]
+        struct Foo {
+            id: u32,
+            #[variants(until(V3))]
+            legacy: String,
+            #[variants(since(V2, default = 7))]
+            revision: u64,
+        }
+
+        let v1 = FooV1 {
+            id: 1,
+            legacy: "old".into(),
+        };
+
+        assert_eq!(v1.migrate_to_latest(), FooV3 { id: 1, revision: 7 });
+    }
+
+    #[test]
+    fn derive_validate() {
+        fn non_empty(value: &String) -> Result<(), &'static str> {
+            match value.is_empty() {
+                true => Err("empty"),
+                false => Ok(()),
+            }
+        }
+
+        fn positive(value: &i32) -> Result<(), &'static str> {
+            match *value > 0 {
+                true => Ok(()),
+                false => Err("not positive"),
+            }
+        }
+
+        #[variants(Create(validate_error = "&'static str"), default = include)]
+        enum Foo {
+            Order {
+                #[variants(include(Create), validate = non_empty)]
+                name: String,
+                #[variants(include(Create), validate = positive)]
+                quantity: i32,
+            },
+            Cancel,
+        }
+
+        let order = FooCreate::Order {
+            name: String::new(),
+            quantity: 0,
+        };
+
+        assert_eq!(
+            order.validate(),
+            Err(vec![("name", "empty"), ("quantity", "not positive")])
+        );
+        assert_eq!(FooCreate::Cancel.validate(), Ok(()));
+    }
+
+    #[test]
+    fn derive_builder() {
+        #[variants(Summary(builder))]
+        #[derive(Debug, PartialEq)]
+        struct Foo {
+            #[variants(include(Summary), retype = "u64")]
+            id: u32,
+            #[variants(include(Summary), default = "unnamed".into())]
+            name: String,
+        }
+
+        assert_eq!(
+            FooSummary::builder().id(1).build(),
+            Ok(FooSummary {
+                id: 1,
+                name: "unnamed".into(),
+            })
+        );
+        assert_eq!(FooSummary::builder().name("foo".into()).build(), Err("id"));
+    }
 }